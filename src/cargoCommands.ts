@@ -1,7 +1,10 @@
 import * as vscode from 'vscode';
 import * as path from 'path';
 import * as fs from 'fs';
-import { discoverWorkspaceMembers, discoverCargoTargets } from './cargoDiscovery';
+import * as os from 'os';
+import { CargoCompilerArtifact } from './types';
+import { getCargoMetadata, getCachedCargoMetadata, getWorkspaceMembers, getCargoTargets } from './cargoMetadata';
+import { isJsonDiagnosticsEnabled, withJsonMessageFormat, runCargoWithDiagnostics } from './cargoDiagnostics';
 
 /**
  * Interface for accessing tree provider's checked items state.
@@ -18,6 +21,443 @@ export interface CargoTreeState {
     refresh(): void;
 }
 
+/**
+ * Expands a leading `~` in a configured path to the user's home directory.
+ */
+export function expandHomePath(configuredPath: string): string {
+    if (configuredPath === '~' || configuredPath.startsWith('~/') || configuredPath.startsWith('~\\')) {
+        return path.join(os.homedir(), configuredPath.slice(1));
+    }
+    return configuredPath;
+}
+
+/**
+ * Appends a cargo invocation to the session log configured in `cargui.sessionLogPath`.
+ * Does nothing when no log path is set, and never interrupts the command if the log can't be written.
+ * 
+ * @param cwd - Directory the command runs in
+ * @param command - Full command line sent to the terminal
+ */
+export function logCargoInvocation(cwd: string, command: string) {
+    const configuredPath = vscode.workspace.getConfiguration('cargui').get<string>('sessionLogPath', '');
+    if (!configuredPath.trim()) {
+        return;
+    }
+
+    const logPath = expandHomePath(configuredPath.trim());
+    try {
+        fs.mkdirSync(path.dirname(logPath), { recursive: true });
+        fs.appendFileSync(logPath, `[${new Date().toISOString()}] cwd=${cwd} ${command}\n`, 'utf-8');
+    } catch (error) {
+        console.error('[cargUI] Failed to write session log:', error);
+    }
+}
+
+/**
+ * Parses a `.env` file with one `KEY=VALUE` pair per line. Blank lines and `#` comments are ignored,
+ * a leading `export ` is allowed, matching surrounding quotes are stripped, and no variable interpolation is performed.
+ * 
+ * @param envFilePath - Path to the .env file
+ * @returns The parsed variables and the 1-based numbers of lines that couldn't be parsed
+ */
+export function parseEnvFile(envFilePath: string): { env: Record<string, string>; invalidLines: number[] } {
+    const env: Record<string, string> = {};
+    const invalidLines: number[] = [];
+    const lines = fs.readFileSync(envFilePath, 'utf-8').split(/\r?\n/);
+
+    lines.forEach((rawLine, index) => {
+        // Files written for `source` prefix each line with export
+        const line = rawLine.trim().replace(/^export\s+/, '');
+        if (!line || line.startsWith('#')) {
+            return;
+        }
+        const separator = line.indexOf('=');
+        const key = separator > 0 ? line.slice(0, separator).trim() : '';
+        if (!/^[A-Za-z_][A-Za-z0-9_]*$/.test(key)) {
+            invalidLines.push(index + 1);
+            return;
+        }
+        let value = line.slice(separator + 1).trim();
+        if (value.length >= 2 && (value[0] === '"' || value[0] === "'") && value[value.length - 1] === value[0]) {
+            value = value.slice(1, -1);
+        }
+        env[key] = value;
+    });
+
+    return { env, invalidLines };
+}
+
+/**
+ * The last .env warning shown, so one action that opens several terminals (or repeated runs of an unchanged file)
+ * doesn't repeat the same popup.
+ */
+let lastEnvFileWarning: string | undefined;
+
+function warnAboutEnvFile(stateKey: string, message: string, quiet: boolean) {
+    if (quiet || lastEnvFileWarning === stateKey) {
+        return;
+    }
+    lastEnvFileWarning = stateKey;
+    vscode.window.showWarningMessage(message);
+}
+
+/**
+ * Loads the variables from the `.env` file configured in `cargui.envFile`, if any.
+ * Relative paths resolve against the package folder the command runs in.
+ * Each problem is reported once until the file changes.
+ * 
+ * @param cwd - Directory the command runs in
+ * @param quiet - Skip warnings, for background checks that don't run a command
+ * @returns Variables to add to the terminal environment, or undefined when no file is configured
+ */
+function loadConfiguredEnvFile(cwd: string, quiet: boolean): Record<string, string> | undefined {
+    const configuredPath = vscode.workspace.getConfiguration('cargui').get<string>('envFile', '').trim();
+    if (!configuredPath) {
+        return undefined;
+    }
+
+    const envFilePath = path.resolve(cwd, expandHomePath(configuredPath));
+    if (!fs.existsSync(envFilePath)) {
+        warnAboutEnvFile(`${envFilePath}:missing`, `cargUI: .env file not found: ${envFilePath}`, quiet);
+        return undefined;
+    }
+
+    const stateKey = `${envFilePath}:${fs.statSync(envFilePath).mtimeMs}`;
+    try {
+        const { env, invalidLines } = parseEnvFile(envFilePath);
+        if (invalidLines.length > 0) {
+            warnAboutEnvFile(
+                stateKey,
+                `cargUI: Skipped invalid lines in ${path.basename(envFilePath)}: ${invalidLines.join(', ')}`,
+                quiet
+            );
+        }
+        return env;
+    } catch (error) {
+        warnAboutEnvFile(stateKey, `cargUI: Failed to read .env file: ${error}`, quiet);
+        return undefined;
+    }
+}
+
+/**
+ * Collects the environment variables cargUI adds to cargo terminals: the `cargui.envFile` variables,
+ * RUSTUP_TOOLCHAIN from `cargui.toolchain`, and RUSTFLAGS/RUSTDOCFLAGS from `cargui.rustflags`/`cargui.rustdocflags`.
+ * 
+ * @param cwd - Directory the command runs in
+ * @param quiet - Don't warn about .env file problems (for toolchain checks and other background calls)
+ * @returns Variables to set on top of the inherited environment
+ */
+export function getCargoTerminalEnv(cwd: string, quiet: boolean = false): Record<string, string> {
+    const env = loadConfiguredEnvFile(cwd, quiet) ?? {};
+    // RUSTUP_TOOLCHAIN is honored by the rustup proxies like `cargo +<toolchain>`, and also covers custom commands
+    const config = vscode.workspace.getConfiguration('cargui');
+    const toolchain = config.get<string>('toolchain', '').trim();
+    if (toolchain) {
+        env.RUSTUP_TOOLCHAIN = toolchain;
+    }
+    const rustflags = config.get<string>('rustflags', '').trim();
+    if (rustflags) {
+        env.RUSTFLAGS = rustflags;
+    }
+    const rustdocflags = config.get<string>('rustdocflags', '').trim();
+    if (rustdocflags) {
+        env.RUSTDOCFLAGS = rustdocflags;
+    }
+    return env;
+}
+
+/**
+ * Set by the startup and toolchain checks when `cargo --version` fails, so no entry point
+ * (keybinding, command palette, custom command) sends cargo to a terminal that can't run it.
+ */
+let cargoMissing = false;
+
+/**
+ * Records whether cargo can be launched; while it can't, sendCargoCommand and friends refuse to run.
+ * 
+ * @param missing - True if the last availability check failed
+ */
+export function setCargoMissing(missing: boolean) {
+    cargoMissing = missing;
+}
+
+function ensureCargoAvailable(): boolean {
+    if (cargoMissing) {
+        vscode.window.showErrorMessage('cargUI: cargo was not found in PATH. Install Rust (rustup.rs) to run cargo commands.');
+        return false;
+    }
+    return true;
+}
+
+/**
+ * Opens a terminal in the given folder and sends a cargo command line to it.
+ * Every command sent through here gets the environment from getCargoTerminalEnv and is
+ * recorded in the session log when one is configured. With `cargui.jsonDiagnostics` on, commands that
+ * support it run with JSON message output in a cargUI-driven terminal instead.
+ * The command also becomes the one Re-run Last Cargo Command sends.
+ * 
+ * @param name - Terminal name
+ * @param cwd - Directory to run the command in
+ * @param command - Full command line to send
+ * @param recordAsLast - Whether Re-run Last Cargo Command should repeat this command (off for toggles like cargo watch)
+ * @returns The terminal the command was sent to, or undefined when cargo isn't available
+ */
+export function sendCargoCommand(name: string, cwd: string, command: string, recordAsLast: boolean = true): vscode.Terminal | undefined {
+    if (!ensureCargoAvailable()) {
+        return undefined;
+    }
+    if (recordAsLast) {
+        lastInvocations = { cwd, invocations: [{ name, command }] };
+    }
+    return launchCargoCommand(name, cwd, command);
+}
+
+/**
+ * The invocations sent by the most recent cargo action, kept so they can be re-sent unchanged.
+ */
+let lastInvocations: { cwd: string; invocations: CargoInvocation[] } | undefined;
+
+/**
+ * Sends several command lines that belong to one action (e.g. one per checked target), each to its own terminal,
+ * and records them together so Re-run Last Cargo Command repeats all of them.
+ * 
+ * @param cwd - Directory to run the commands in
+ * @param invocations - Command lines and terminal names
+ */
+export function sendCargoCommands(cwd: string, invocations: CargoInvocation[]) {
+    if (invocations.length === 0 || !ensureCargoAvailable()) {
+        return;
+    }
+    lastInvocations = { cwd, invocations };
+    for (const invocation of invocations) {
+        launchCargoCommand(invocation.name, cwd, invocation.command);
+    }
+}
+
+function launchCargoCommand(name: string, cwd: string, command: string): vscode.Terminal {
+    const jsonCommand = isJsonDiagnosticsEnabled() ? withJsonMessageFormat(command) : undefined;
+    if (jsonCommand) {
+        logCargoInvocation(cwd, jsonCommand);
+        return runCargoWithDiagnostics(name, cwd, jsonCommand, getCargoTerminalEnv(cwd));
+    }
+
+    const terminal = vscode.window.createTerminal({ name, cwd, env: getCargoTerminalEnv(cwd) });
+    terminal.show();
+    terminal.sendText(command);
+    logCargoInvocation(cwd, command);
+    return terminal;
+}
+
+/**
+ * Spells out a command line the way launchCargoCommand would run it, for previewing or pasting into another shell:
+ * `--message-format` is added when JSON diagnostics are on, and the environment cargUI sets on its terminals
+ * (.env file, toolchain, RUSTFLAGS) is prefixed, as `KEY=value` on POSIX shells or `$env:KEY` on Windows.
+ * 
+ * @param cwd - Directory the command would run in
+ * @param command - Command line as built for the terminal
+ * @returns The command with its environment prefix
+ */
+export function describeCargoInvocation(cwd: string, command: string): string {
+    const runCommand = (isJsonDiagnosticsEnabled() ? withJsonMessageFormat(command) : undefined) ?? command;
+    const env = Object.entries(getCargoTerminalEnv(cwd, true));
+    if (env.length === 0) {
+        return runCommand;
+    }
+    if (process.platform === 'win32') {
+        const assignments = env.map(([key, value]) => `$env:${key}="${value.replace(/"/g, '`"')}"`);
+        return `${assignments.join('; ')}; ${runCommand}`;
+    }
+    const assignments = env.map(([key, value]) => `${key}=${quoteShellArgument(value)}`);
+    return `${assignments.join(' ')} ${runCommand}`;
+}
+
+/**
+ * Cargo subcommands that compile the package and therefore accept feature, profile and target flags.
+ */
+const COMPILING_ACTIONS = ['build', 'run', 'test', 'bench', 'check', 'clippy', 'doc', 'fix'];
+
+/**
+ * Picks the profile flag for a command: the custom profile from `cargui.customProfile` when one is set,
+ * otherwise `--release` in release mode. Cargo rejects `--release` together with `--profile`, so only one is returned.
+ * 
+ * @param action - Cargo subcommand the flag is for
+ * @param release - Whether release mode is on for this command
+ * @returns The flag with a leading space, or an empty string for debug builds
+ */
+export function getProfileFlag(action: string, release: boolean): string {
+    const customProfile = vscode.workspace.getConfiguration('cargui').get<string>('customProfile', '').trim();
+    if (customProfile && COMPILING_ACTIONS.includes(action)) {
+        return ` --profile ${customProfile}`;
+    }
+    return release ? ' --release' : '';
+}
+
+/**
+ * Builds the cargo flags that come from cargUI settings rather than tree checkboxes,
+ * so every runner applies them the same way. Per-command extras are added last by appendCommandArguments.
+ * 
+ * @param action - Cargo subcommand the flags are for (e.g., "build", "clean")
+ * @returns Flags with a leading space, or an empty string when none apply
+ */
+export function getSettingsCargoFlags(action: string): string {
+    const config = vscode.workspace.getConfiguration('cargui');
+    let flags = '';
+
+    if (COMPILING_ACTIONS.includes(action)) {
+        // A single setting holds the mode, so --all-features and --no-default-features can never be combined
+        const featureMode = config.get<string>('featureMode', 'default');
+        if (featureMode === 'allFeatures') {
+            flags += ' --all-features';
+        } else if (featureMode === 'noDefaultFeatures') {
+            flags += ' --no-default-features';
+        }
+
+        const targetTriple = config.get<string>('targetTriple', '').trim();
+        if (targetTriple) {
+            flags += ` --target ${targetTriple}`;
+        }
+
+        // 0 leaves the job count to cargo (one per CPU)
+        const jobs = config.get<number>('jobs', 0);
+        if (jobs > 0) {
+            flags += ` -j ${jobs}`;
+        }
+    }
+
+    // rustfmt doesn't go through cargo's dependency resolution, so cargo fmt rejects these flags
+    const networkMode = config.get<string>('networkMode', 'default');
+    if (action === 'update') {
+        // cargo update rewrites Cargo.lock, which --locked forbids; frozen still means no network access
+        if (networkMode === 'offline' || networkMode === 'frozen') {
+            flags += ' --offline';
+        }
+    } else if (networkMode !== 'default' && action !== 'fmt') {
+        flags += ` --${networkMode}`;
+    }
+
+    // cargo fmt forwards --config to rustfmt, where it means something else
+    const configOverrides = config.get<string[]>('configOverrides', []);
+    if (action !== 'fmt') {
+        for (const override of configOverrides) {
+            if (override.trim()) {
+                flags += ` --config ${quoteShellArgument(override.trim())}`;
+            }
+        }
+    }
+
+    return flags;
+}
+
+/**
+ * Appends the extra arguments configured for one command in `cargui.commandArguments` (e.g., "--all-targets" for check)
+ * after every flag cargUI adds. A `--` in the extras is merged with the command's own `--` segment, so cargo flags
+ * stay in front of it and program arguments follow it.
+ * 
+ * @param command - Fully built command line, without environment variable prefixes
+ * @param action - Cargo subcommand the extras are configured for
+ * @returns The command line with the extras added
+ */
+export function appendCommandArguments(command: string, action: string): string {
+    const commandArguments = vscode.workspace.getConfiguration('cargui').get<{ [action: string]: string }>('commandArguments', {});
+    const extraArguments = commandArguments[action]?.trim();
+    if (!extraArguments) {
+        return command;
+    }
+
+    const [cargoPart, programPart] = splitAtArgumentSeparator(command);
+    const [extraCargoPart, extraProgramPart] = splitAtArgumentSeparator(extraArguments);
+    let result = extraCargoPart ? `${cargoPart} ${extraCargoPart}` : cargoPart;
+    if (programPart !== undefined || extraProgramPart !== undefined) {
+        const programArguments = [programPart, extraProgramPart].filter(part => part).join(' ');
+        result += programArguments ? ` -- ${programArguments}` : ' --';
+    }
+    return result;
+}
+
+/**
+ * Splits a command line at its first standalone `--`.
+ * 
+ * @returns The part before it, and the part after it (undefined when there is no `--`)
+ */
+function splitAtArgumentSeparator(commandLine: string): [string, string | undefined] {
+    const match = commandLine.match(/(^|\s)--(\s|$)/);
+    if (!match || match.index === undefined) {
+        return [commandLine.trim(), undefined];
+    }
+    return [commandLine.slice(0, match.index).trim(), commandLine.slice(match.index + match[0].length).trim()];
+}
+
+/**
+ * Name of the folder under the target directory a build writes to: `debug` or `release`, or the custom profile's name.
+ * Cargo keeps `debug` for the built-in dev and test profiles and `release` for bench.
+ * 
+ * @param release - Whether release mode is on for this command
+ * @returns Output folder name (e.g., "debug", "release", "profiling")
+ */
+export function getProfileOutputDir(release: boolean): string {
+    const customProfile = vscode.workspace.getConfiguration('cargui').get<string>('customProfile', '').trim();
+    if (customProfile === 'dev' || customProfile === 'test') {
+        return 'debug';
+    }
+    if (customProfile === 'bench') {
+        return 'release';
+    }
+    return customProfile || (release ? 'release' : 'debug');
+}
+
+/**
+ * Folder that holds the profile and doc output: cargo nests it under the triple when `cargui.targetTriple` is set.
+ * 
+ * @param targetDir - Cargo's target directory
+ * @returns `<targetDir>/<triple>`, or targetDir itself for host builds
+ */
+export function getTargetOutputRoot(targetDir: string): string {
+    const targetTriple = vscode.workspace.getConfiguration('cargui').get<string>('targetTriple', '').trim();
+    return targetTriple ? path.join(targetDir, targetTriple) : targetDir;
+}
+
+/**
+ * Quotes a value for the integrated terminal's shell when it contains spaces or shell metacharacters.
+ * Windows shells get double quotes (paths can't contain them); POSIX shells get single quotes.
+ * 
+ * @param value - Value to insert into a command line (e.g., a path)
+ * @returns The value, quoted if needed
+ */
+export function quoteShellArgument(value: string): string {
+    // Backslashes are path separators on Windows but escapes in POSIX shells
+    const safe = process.platform === 'win32' ? /^[\w@%+=:,./\\-]+$/ : /^[\w@%+=:,./-]+$/;
+    if (safe.test(value)) {
+        return value;
+    }
+    if (process.platform === 'win32') {
+        return `"${value}"`;
+    }
+    return `'${value.replace(/'/g, `'\\''`)}'`;
+}
+
+/**
+ * Expands placeholders in program arguments, so run configurations can reference paths without hardcoding them:
+ * `{workspace}` (package folder), `{profile}` (output folder name: debug, release, or the custom profile)
+ * and `{target_dir}` (cargo's target directory). Paths are shell-quoted, so folders with spaces stay one argument.
+ * 
+ * @param args - Checked program arguments
+ * @param workspacePath - Package folder the command runs in
+ * @param release - Whether release mode is on for this command
+ * @returns Arguments with placeholders replaced
+ */
+export function expandArgumentPlaceholders(args: string[], workspacePath: string, release: boolean): string[] {
+    const profile = getProfileOutputDir(release);
+    // Metadata is normally loaded by the time a command runs; until then use cargo's own defaults
+    const targetDir = getCachedCargoMetadata(workspacePath)?.target_directory
+        ?? process.env.CARGO_TARGET_DIR
+        ?? path.join(workspacePath, 'target');
+
+    return args.map(arg => arg
+        .replace(/\{workspace\}/g, quoteShellArgument(workspacePath))
+        .replace(/\{profile\}/g, profile)
+        .replace(/\{target_dir\}/g, quoteShellArgument(targetDir)));
+}
+
 /**
  * Builds cargo targets with a specific feature enabled.
  * 
@@ -40,11 +480,11 @@ export function buildWithFeature(
 
     // Get member path if specific member is selected
     const memberPath = selectedWorkspaceMember && selectedWorkspaceMember !== 'all'
-        ? discoverWorkspaceMembers(workspaceFolder.uri.fsPath).find(m => m.name === selectedWorkspaceMember)?.path
+        ? getWorkspaceMembers(workspaceFolder.uri.fsPath).find(m => m.name === selectedWorkspaceMember)?.path
         : undefined;
 
     const checkedTargets = treeProvider.getCheckedTargets();
-    const allTargets = discoverCargoTargets(workspaceFolder.uri.fsPath, memberPath);
+    const allTargets = getCargoTargets(workspaceFolder.uri.fsPath, memberPath);
     
     // If no targets are checked, build all with this feature
     let targetsToRun: string[] = [];
@@ -58,6 +498,7 @@ export function buildWithFeature(
     }
 
     // Build command for each target with this feature
+    const invocations: CargoInvocation[] = [];
     for (const targetName of targetsToRun) {
         const target = allTargets.find(t => t.name === targetName);
         if (!target) continue;
@@ -85,21 +526,17 @@ export function buildWithFeature(
             command += ` --bench ${targetName}`;
         }
 
-        if (release) {
-            command += ' --release';
-        }
+        command += getProfileFlag('build', release);
+        command += getSettingsCargoFlags('build');
 
         // Add this specific feature
         command += ` --features ${featureName}`;
+        command = appendCommandArguments(command, 'build');
 
-        // Create and show terminal
-        const terminal = vscode.window.createTerminal({
-            name: `Cargo build: ${targetName} (${featureName})`,
-            cwd: workspaceFolder.uri.fsPath
-        });
-        terminal.show();
-        terminal.sendText(command);
+        invocations.push({ name: `Cargo build: ${targetName} (${featureName})`, command });
     }
+
+    sendCargoCommands(workspaceFolder.uri.fsPath, invocations);
 }
 
 /**
@@ -125,7 +562,7 @@ export function runCargoTarget(
     }
 
     // Discover targets to check if this is src/main.rs
-    const allTargets = discoverCargoTargets(workspaceFolder.uri.fsPath);
+    const allTargets = getCargoTargets(workspaceFolder.uri.fsPath);
     const currentTarget = allTargets.find(t => t.name === targetName && t.type === targetType);
 
     let command = '';
@@ -150,9 +587,11 @@ export function runCargoTarget(
             break;
     }
 
-    if (release && (targetType === 'bin' || targetType === 'example')) {
-        command += ' --release';
-    }
+    const targetAction = targetType === 'test' || targetType === 'bench' ? targetType : 'run';
+    // Only run targets honor release mode here, and {profile} must match the folder that actually gets built
+    const profileRelease = release && (targetType === 'bin' || targetType === 'example');
+    command += getProfileFlag(targetAction, profileRelease);
+    command += getSettingsCargoFlags(targetAction);
 
     // Merge checked features with required features (no duplicates)
     const checkedFeatures = cargoTreeProvider.getCheckedFeatures();
@@ -162,11 +601,12 @@ export function runCargoTarget(
     }
 
     // Add checked arguments (prefix each with -- and no space between -- and arg)
-    const checkedArgs = cargoTreeProvider.getCheckedArguments();
+    const checkedArgs = expandArgumentPlaceholders(cargoTreeProvider.getCheckedArguments(), workspaceFolder.uri.fsPath, profileRelease);
     if (checkedArgs.length > 0) {
         const formattedArgs = checkedArgs.map(arg => `--${arg}`).join(' ');
         command += ` -- ${formattedArgs}`;
     }
+    command = appendCommandArguments(command, targetAction);
 
     // Prepend checked environment variables to command
     const checkedEnvVars = cargoTreeProvider.getCheckedEnvVars();
@@ -174,13 +614,7 @@ export function runCargoTarget(
         command = `${checkedEnvVars.join(' ')} ${command}`;
     }
 
-    // Create and show terminal
-    const terminal = vscode.window.createTerminal({
-        name: `Cargo ${targetType}: ${targetName}`,
-        cwd: workspaceFolder.uri.fsPath
-    });
-    terminal.show();
-    terminal.sendText(command);
+    sendCargoCommand(`Cargo ${targetType}: ${targetName}`, workspaceFolder.uri.fsPath, command);
 }
 
 /**
@@ -217,7 +651,7 @@ export function buildSingleTarget(
     }
     
     // Discover targets to check if this is src/main.rs
-    const allTargets = discoverCargoTargets(workspaceFolder.uri.fsPath, selectedWorkspaceMember);
+    const allTargets = getCargoTargets(workspaceFolder.uri.fsPath, selectedWorkspaceMember);
     const currentTarget = allTargets.find(t => t.name === targetName && t.type === targetType);
     
     // Add target-specific flags
@@ -235,9 +669,8 @@ export function buildSingleTarget(
         command += ` --bench ${targetName}`;
     }
 
-    if (release) {
-        command += ' --release';
-    }
+    command += getProfileFlag('build', release);
+    command += getSettingsCargoFlags('build');
 
     // Merge checked features with required features (no duplicates)
     if (cargoTreeProvider) {
@@ -247,37 +680,43 @@ export function buildSingleTarget(
             command += ` --features ${allFeatures.join(',')}`;
         }
     }
+    command = appendCommandArguments(command, 'build');
 
-    // Create and show terminal
-    const terminal = vscode.window.createTerminal({
-        name: `Cargo build: ${targetName}`,
-        cwd: workspaceFolder.uri.fsPath
-    });
-    terminal.show();
-    terminal.sendText(command);
+    sendCargoCommand(`Cargo build: ${targetName}`, workspaceFolder.uri.fsPath, command);
 }
 
 /**
- * Runs a cargo command on checked targets or workspace members.
- * Handles workspace-level, member-level, and target-level execution with features, args, and env vars.
+ * A fully assembled cargo command line and the name of the terminal it runs in.
+ */
+export interface CargoInvocation {
+    name: string;
+    command: string;
+}
+
+/**
+ * Builds the cargo command lines for checked targets or workspace members without running them.
+ * Handles workspace-level, member-level, and target-level commands with features, args, and env vars.
  * 
- * @param action - Cargo action to run (build, run, test, bench, check, etc.)
- * @param release - Whether to run in release mode
+ * @param action - Cargo action to build commands for (build, run, test, bench, check, etc.)
+ * @param release - Whether to use release mode
  * @param treeProvider - Tree provider for accessing checked items
  * @param selectedWorkspaceMember - Currently selected workspace member
+ * @returns Invocations in the order they would run (empty if no targets were found)
  */
-export async function runCargoCommandOnTargets(
+export function buildCargoCommandsOnTargets(
     action: string, 
     release: boolean, 
     treeProvider: CargoTreeState,
     selectedWorkspaceMember?: string
-) {
+): CargoInvocation[] {
     const workspaceFolder = treeProvider.getWorkspaceFolder();
     if (!workspaceFolder) {
-        vscode.window.showErrorMessage('No workspace folder found');
-        return;
+        return [];
     }
 
+    // Release mode only applies to these actions; {profile} in program arguments follows the same rule
+    const profileRelease = release && ['build', 'run', 'test', 'bench'].includes(action);
+
     // Get checked workspace members
     const checkedMembers = treeProvider.getCheckedWorkspaceMembers();
     
@@ -285,9 +724,8 @@ export async function runCargoCommandOnTargets(
     if (selectedWorkspaceMember === 'all') {
         let command = `cargo ${action} --workspace`;
         
-        if (release && ['build', 'run', 'test', 'bench'].includes(action)) {
-            command += ' --release';
-        }
+        command += getProfileFlag(action, profileRelease);
+        command += getSettingsCargoFlags(action);
 
         // Add features
         const checkedFeatures = treeProvider.getCheckedFeatures();
@@ -297,11 +735,12 @@ export async function runCargoCommandOnTargets(
 
         // Add arguments for run/test/bench
         if (['run', 'test', 'bench'].includes(action)) {
-            const checkedArgs = treeProvider.getCheckedArguments();
+            const checkedArgs = expandArgumentPlaceholders(treeProvider.getCheckedArguments(), workspaceFolder.uri.fsPath, profileRelease);
             if (checkedArgs.length > 0) {
                 command += ` -- ${checkedArgs.join(' ')}`;
             }
         }
+        command = appendCommandArguments(command, action);
 
         // Prepend environment variables
         const checkedEnvVars = treeProvider.getCheckedEnvVars();
@@ -309,54 +748,11 @@ export async function runCargoCommandOnTargets(
             command = `${checkedEnvVars.join(' ')} ${command}`;
         }
 
-        const terminal = vscode.window.createTerminal({
-            name: `Cargo ${action}: All`,
-            cwd: workspaceFolder.uri.fsPath
-        });
-        terminal.show();
-        terminal.sendText(command);
-        return;
+        return [{ name: `Cargo ${action}: All`, command }];
     }
 
     // Handle checked workspace members (multiple --package flags)
     if (checkedMembers.length > 0) {
-        // Check if selected member is NOT in checked members (potential confusion)
-        if (selectedWorkspaceMember && 
-            selectedWorkspaceMember !== 'all' && 
-            !checkedMembers.includes(selectedWorkspaceMember)) {
-            
-            // Check if user wants to be warned about this
-            const config = vscode.workspace.getConfiguration('cargui');
-            const dontWarn = config.get<boolean>('dontWarnCheckedOverSelected', false);
-            
-            if (!dontWarn) {
-                const choice = await vscode.window.showWarningMessage(
-                    `You have "${selectedWorkspaceMember}" selected but only checked members will be built. Include selected member?`,
-                    { modal: false },
-                    'No',
-                    'Yes',
-                    "Don't Ask Again"
-                );
-                
-                if (choice === 'Yes') {
-                    // Add selected member to checked members and update UI
-                    treeProvider.setWorkspaceMemberChecked(selectedWorkspaceMember, true);
-                    // Refresh to show the new check
-                    treeProvider.refresh();
-                    // Wait a bit for UI to update
-                    await new Promise(resolve => setTimeout(resolve, 100));
-                    // Re-fetch checked members after update
-                    checkedMembers.push(selectedWorkspaceMember);
-                } else if (choice === "Don't Ask Again") {
-                    await config.update('dontWarnCheckedOverSelected', true, vscode.ConfigurationTarget.Global);
-                } else if (choice === undefined) {
-                    // User dismissed the dialog - cancel the action
-                    return;
-                }
-                // If "No", continue with just the checked members
-            }
-        }
-        
         let command = `cargo ${action}`;
         
         // Add --package flag for each checked member
@@ -364,9 +760,8 @@ export async function runCargoCommandOnTargets(
             command += ` --package ${member}`;
         }
         
-        if (release && ['build', 'run', 'test', 'bench'].includes(action)) {
-            command += ' --release';
-        }
+        command += getProfileFlag(action, profileRelease);
+        command += getSettingsCargoFlags(action);
 
         // Add features
         const checkedFeatures = treeProvider.getCheckedFeatures();
@@ -376,11 +771,12 @@ export async function runCargoCommandOnTargets(
 
         // Add arguments for run/test/bench
         if (['run', 'test', 'bench'].includes(action)) {
-            const checkedArgs = treeProvider.getCheckedArguments();
+            const checkedArgs = expandArgumentPlaceholders(treeProvider.getCheckedArguments(), workspaceFolder.uri.fsPath, profileRelease);
             if (checkedArgs.length > 0) {
                 command += ` -- ${checkedArgs.join(' ')}`;
             }
         }
+        command = appendCommandArguments(command, action);
 
         // Prepend environment variables
         const checkedEnvVars = treeProvider.getCheckedEnvVars();
@@ -388,23 +784,46 @@ export async function runCargoCommandOnTargets(
             command = `${checkedEnvVars.join(' ')} ${command}`;
         }
 
-        const terminal = vscode.window.createTerminal({
-            name: `Cargo ${action}: ${checkedMembers.join(', ')}`,
-            cwd: workspaceFolder.uri.fsPath
-        });
-        terminal.show();
-        terminal.sendText(command);
-        return;
+        return [{ name: `Cargo ${action}: ${checkedMembers.join(', ')}`, command }];
+    }
+
+    const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
+    const checkedTargets = treeProvider.getCheckedTargets();
+
+    // A virtual manifest has no targets of its own. With nothing selected, let cargo pick the packages:
+    // it uses workspace.default-members when declared, otherwise every member.
+    const isVirtualManifest = workspaceMembers.length > 0 && !workspaceMembers.some(m => m.isRoot);
+    if (isVirtualManifest && !selectedWorkspaceMember && checkedTargets.length === 0) {
+        let command = `cargo ${action}`;
+
+        command += getProfileFlag(action, profileRelease);
+        command += getSettingsCargoFlags(action);
+
+        // Add arguments for run/test/bench
+        if (['run', 'test', 'bench'].includes(action)) {
+            const checkedArgs = expandArgumentPlaceholders(treeProvider.getCheckedArguments(), workspaceFolder.uri.fsPath, profileRelease);
+            if (checkedArgs.length > 0) {
+                command += ` -- ${checkedArgs.join(' ')}`;
+            }
+        }
+        command = appendCommandArguments(command, action);
+
+        // Prepend environment variables
+        const checkedEnvVars = treeProvider.getCheckedEnvVars();
+        if (checkedEnvVars.length > 0) {
+            command = `${checkedEnvVars.join(' ')} ${command}`;
+        }
+
+        return [{ name: `Cargo ${action}: default members`, command }];
     }
 
     // Get member path if specific member is selected
     const memberPath = selectedWorkspaceMember
-        ? discoverWorkspaceMembers(workspaceFolder.uri.fsPath).find(m => m.name === selectedWorkspaceMember)?.path
+        ? workspaceMembers.find(m => m.name === selectedWorkspaceMember)?.path
         : undefined;
 
-    const checkedTargets = treeProvider.getCheckedTargets();
     const checkedFeatures = treeProvider.getCheckedFeatures();
-    const allTargets = discoverCargoTargets(workspaceFolder.uri.fsPath, memberPath);
+    const allTargets = getCargoTargets(workspaceFolder.uri.fsPath, memberPath);
     
     // If no targets are checked, use main target (src/main.rs or src/lib.rs)
     let targetsToRun: string[] = [];
@@ -419,14 +838,14 @@ export async function runCargoCommandOnTargets(
             targetsToRun = [mainTarget.name];
         } else {
             // No targets found at all
-            vscode.window.showErrorMessage('No targets found to run');
-            return;
+            return [];
         }
     } else {
         targetsToRun = checkedTargets;
     }
 
-    // Run command for each target
+    // Build a command for each target
+    const invocations: CargoInvocation[] = [];
     for (const targetName of targetsToRun) {
         const target = allTargets.find(t => t.name === targetName);
         if (!target) continue;
@@ -457,9 +876,8 @@ export async function runCargoCommandOnTargets(
             command += ` --bench ${targetName}`;
         }
 
-        if (release && ['build', 'run', 'test', 'bench'].includes(action)) {
-            command += ' --release';
-        }
+        command += getProfileFlag(action, profileRelease);
+        command += getSettingsCargoFlags(action);
 
         // Add features flag if any features are checked
         if (checkedFeatures.length > 0) {
@@ -468,11 +886,12 @@ export async function runCargoCommandOnTargets(
 
         // Add checked arguments (only for run, test, bench)
         if (['run', 'test', 'bench'].includes(action)) {
-            const checkedArgs = treeProvider.getCheckedArguments();
+            const checkedArgs = expandArgumentPlaceholders(treeProvider.getCheckedArguments(), workspaceFolder.uri.fsPath, profileRelease);
             if (checkedArgs.length > 0) {
                 command += ` -- ${checkedArgs.join(' ')}`;
             }
         }
+        command = appendCommandArguments(command, action);
 
         // Prepend checked environment variables to command
         const checkedEnvVars = treeProvider.getCheckedEnvVars();
@@ -480,14 +899,101 @@ export async function runCargoCommandOnTargets(
             command = `${checkedEnvVars.join(' ')} ${command}`;
         }
 
-        // Create and show terminal
-        const terminal = vscode.window.createTerminal({
-            name: `Cargo ${action}: ${targetName}`,
-            cwd: workspaceFolder.uri.fsPath
-        });
-        terminal.show();
-        terminal.sendText(command);
+        invocations.push({ name: `Cargo ${action}: ${targetName}`, command });
     }
+
+    return invocations;
+}
+
+/**
+ * Re-sends the exact command lines of the most recent cargo action, without re-reading checked
+ * targets, features, or arguments from the tree.
+ * 
+ * @returns False if no cargo action has been run yet in this session
+ */
+export function rerunLastCargoCommands(): boolean {
+    if (!lastInvocations) {
+        return false;
+    }
+    if (!ensureCargoAvailable()) {
+        return true;
+    }
+    for (const invocation of lastInvocations.invocations) {
+        launchCargoCommand(invocation.name, lastInvocations.cwd, invocation.command);
+    }
+    return true;
+}
+
+/**
+ * Runs a cargo command on checked targets or workspace members.
+ * Confirms ambiguous member selections, then sends each built command to its own terminal.
+ * 
+ * @param action - Cargo action to run (build, run, test, bench, check, etc.)
+ * @param release - Whether to run in release mode
+ * @param treeProvider - Tree provider for accessing checked items
+ * @param selectedWorkspaceMember - Currently selected workspace member
+ * @param preparedInvocations - Command lines already built for this selection (e.g. shown in a preview); rebuilt if the confirmation changes the checked members
+ */
+export async function runCargoCommandOnTargets(
+    action: string, 
+    release: boolean, 
+    treeProvider: CargoTreeState,
+    selectedWorkspaceMember?: string,
+    preparedInvocations?: CargoInvocation[]
+) {
+    const workspaceFolder = treeProvider.getWorkspaceFolder();
+    if (!workspaceFolder) {
+        vscode.window.showErrorMessage('No workspace folder found');
+        return;
+    }
+
+    // Get checked workspace members
+    const checkedMembers = treeProvider.getCheckedWorkspaceMembers();
+    
+    // Check if selected member is NOT in checked members (potential confusion)
+    if (selectedWorkspaceMember !== 'all' &&
+        checkedMembers.length > 0 &&
+        selectedWorkspaceMember && 
+        !checkedMembers.includes(selectedWorkspaceMember)) {
+        
+        // Check if user wants to be warned about this
+        const config = vscode.workspace.getConfiguration('cargui');
+        const dontWarn = config.get<boolean>('dontWarnCheckedOverSelected', false);
+        
+        if (!dontWarn) {
+            const choice = await vscode.window.showWarningMessage(
+                `You have "${selectedWorkspaceMember}" selected but only checked members will be built. Include selected member?`,
+                { modal: false },
+                'No',
+                'Yes',
+                "Don't Ask Again"
+            );
+            
+            if (choice === 'Yes') {
+                // Add selected member to checked members and update UI
+                treeProvider.setWorkspaceMemberChecked(selectedWorkspaceMember, true);
+                preparedInvocations = undefined;
+                // Refresh to show the new check
+                treeProvider.refresh();
+                // Wait a bit for UI to update
+                await new Promise(resolve => setTimeout(resolve, 100));
+            } else if (choice === "Don't Ask Again") {
+                await config.update('dontWarnCheckedOverSelected', true, vscode.ConfigurationTarget.Global);
+            } else if (choice === undefined) {
+                // User dismissed the dialog - cancel the action
+                return;
+            }
+            // If "No", continue with just the checked members
+        }
+    }
+
+    const invocations = preparedInvocations ?? buildCargoCommandsOnTargets(action, release, treeProvider, selectedWorkspaceMember);
+    if (invocations.length === 0) {
+        vscode.window.showErrorMessage('No targets found to run');
+        return;
+    }
+
+    sendCargoCommands(workspaceFolder.uri.fsPath, invocations);
 }
 
 /**
@@ -513,18 +1019,166 @@ export function runCargoCommand(action: string, release: boolean = false, args?:
     }
 
     let command = `cargo ${action}`;
-    if (release && ['build', 'run', 'test', 'bench'].includes(action)) {
-        command += ' --release';
-    }
+    command += getProfileFlag(action, release && ['build', 'run', 'test', 'bench'].includes(action));
+    command += getSettingsCargoFlags(action);
     if (args && args.trim()) {
         command += ` ${args.trim()}`;
     }
+    command = appendCommandArguments(command, action);
+
+    sendCargoCommand(`Cargo ${action}`, workspaceFolder.uri.fsPath, command);
+}
+
+/**
+ * Summary of the machine-applicable suggestions clippy would apply with `--fix`.
+ */
+export interface ClippyFixSummary {
+    fixCount: number;
+    files: string[];
+}
+
+/**
+ * Runs clippy in JSON mode without applying anything and collects the machine-applicable suggestions.
+ * This lets the user see how many fixes `cargo clippy --fix` would make before any file is rewritten.
+ * 
+ * @param cwd - Directory to run cargo clippy in
+ * @param packageArgs - Optional package selection flags (e.g., "--package core" or "--workspace")
+ * @returns Promise resolving to the number of fixes and the files they touch
+ */
+export function collectClippyFixes(cwd: string, packageArgs?: string): Promise<ClippyFixSummary> {
+    return new Promise((resolve, reject) => {
+        const { exec } = require('child_process');
+        let command = 'cargo clippy --message-format json';
+        if (packageArgs) {
+            command += ` ${packageArgs}`;
+        }
+        // Same features, target and config overrides as the --fix run, so the preview counts the same suggestions
+        command += getSettingsCargoFlags('clippy');
+
+        exec(command, { cwd, env: { ...process.env, ...getCargoTerminalEnv(cwd, true) }, maxBuffer: 1024 * 1024 * 50 }, (error: any, stdout: string, stderr: string) => {
+            // clippy exits non-zero when denied lints fire, but the JSON on stdout is still valid
+            if (error && !stdout.trim()) {
+                reject(stderr.trim() || error.message);
+                return;
+            }
+
+            // The same suggestion is reported once per target (lib, bin, tests), so we dedupe by location
+            const suggestions = new Set<string>();
+            const files = new Set<string>();
+            const collectSpans = (spans: any[] | undefined) => {
+                for (const span of spans || []) {
+                    if (span.suggestion_applicability === 'MachineApplicable' && span.suggested_replacement !== null && span.suggested_replacement !== undefined) {
+                        suggestions.add(`${span.file_name}:${span.line_start}:${span.column_start}:${span.suggested_replacement}`);
+                        files.add(span.file_name);
+                    }
+                }
+            };
 
-    // Create and show terminal
-    const terminal = vscode.window.createTerminal({
-        name: `Cargo ${action}`,
-        cwd: workspaceFolder.uri.fsPath
+            for (const line of stdout.split('\n')) {
+                if (!line.startsWith('{')) {
+                    continue;
+                }
+                try {
+                    const parsed = JSON.parse(line);
+                    if (parsed.reason !== 'compiler-message' || !parsed.message) {
+                        continue;
+                    }
+                    collectSpans(parsed.message.spans);
+                    for (const child of parsed.message.children || []) {
+                        collectSpans(child.spans);
+                    }
+                } catch (parseError) {
+                    // Ignore lines that are not cargo JSON messages
+                }
+            }
+
+            resolve({ fixCount: suggestions.size, files: Array.from(files).sort() });
+        });
     });
-    terminal.show();
-    terminal.sendText(command);
+}
+
+/**
+ * Size of a built binary in the cargo target directory.
+ */
+export interface ArtifactSize {
+    name: string;
+    path: string;
+    bytes: number;
+}
+
+/**
+ * Asks cargo for the target directory so CARGO_TARGET_DIR and `.cargo/config.toml` overrides are respected.
+ * Falls back to `<workspace>/target` when cargo metadata can't be run.
+ * 
+ * @param cwd - Package or workspace root
+ * @returns Promise resolving to the absolute target directory
+ */
+export async function getTargetDirectory(cwd: string): Promise<string> {
+    const metadata = await getCargoMetadata(cwd);
+    return metadata?.target_directory ?? path.join(cwd, 'target');
+}
+
+/**
+ * Finds the built binaries for every bin target in the package and its workspace members.
+ * 
+ * @param workspacePath - Root of the package or workspace
+ * @param targetDir - Cargo target directory
+ * @param profileDir - Profile output folder (e.g., "debug" or "release")
+ * @returns Sizes of the binaries that exist on disk, sorted by name
+ */
+export function collectArtifactSizes(workspacePath: string, targetDir: string, profileDir: string): ArtifactSize[] {
+    const memberPaths = getWorkspaceMembers(workspacePath).map(m => m.path);
+    if (memberPaths.length === 0) {
+        memberPaths.push('.');
+    }
+
+    const binaryNames = new Set<string>();
+    for (const memberPath of memberPaths) {
+        getCargoTargets(workspacePath, memberPath === '.' ? undefined : memberPath)
+            .filter(t => t.type === 'bin')
+            .forEach(t => binaryNames.add(t.name));
+    }
+
+    const extension = process.platform === 'win32' ? '.exe' : '';
+    const artifacts: ArtifactSize[] = [];
+    for (const name of binaryNames) {
+        const binaryPath = path.join(targetDir, profileDir, `${name}${extension}`);
+        if (fs.existsSync(binaryPath)) {
+            artifacts.push({ name, path: binaryPath, bytes: fs.statSync(binaryPath).size });
+        }
+    }
+
+    return artifacts.sort((a, b) => a.name.localeCompare(b.name));
+}
+
+/**
+ * Gets the sizes of the executables a cargo run produced, from its `compiler-artifact` messages.
+ * 
+ * @param artifacts - Artifacts collected by a JSON diagnostics run
+ * @returns Sizes of the executables that exist on disk, named after their cargo target and sorted by name
+ */
+export function collectBuiltArtifactSizes(artifacts: CargoCompilerArtifact[]): ArtifactSize[] {
+    const sizes = new Map<string, ArtifactSize>();
+    for (const artifact of artifacts) {
+        const executable = artifact.executable;
+        if (executable && !sizes.has(executable) && fs.existsSync(executable)) {
+            sizes.set(executable, { name: artifact.target.name, path: executable, bytes: fs.statSync(executable).size });
+        }
+    }
+    return [...sizes.values()].sort((a, b) => a.name.localeCompare(b.name));
+}
+
+/**
+ * Formats a byte count for display (e.g., "1.4 MB").
+ */
+export function formatBytes(bytes: number): string {
+    const units = ['B', 'KB', 'MB', 'GB'];
+    let value = Math.abs(bytes);
+    let unitIndex = 0;
+    while (value >= 1024 && unitIndex < units.length - 1) {
+        value /= 1024;
+        unitIndex++;
+    }
+    const formatted = unitIndex === 0 ? `${value}` : value.toFixed(1);
+    return `${bytes < 0 ? '-' : ''}${formatted} ${units[unitIndex]}`;
 }
@@ -0,0 +1,323 @@
+import * as vscode from 'vscode';
+import * as path from 'path';
+import { CargoMessage, CargoCompilerMessage, CargoCompilerArtifact, CargoDiagnostic } from './types';
+import { getCargoMetadata, getCachedCargoMetadata } from './cargoMetadata';
+
+/**
+ * Cargo subcommands that accept `--message-format` and compile without handing the terminal to a program.
+ * `run` is left out so interactive binaries keep a real terminal for stdin.
+ */
+const DIAGNOSTIC_ACTIONS = ['build', 'check', 'clippy', 'test', 'bench'];
+
+const MESSAGE_FORMAT_FLAG = '--message-format=json-diagnostic-rendered-ansi';
+
+/**
+ * Everything a JSON diagnostics run produced, handed to listeners once cargo exits.
+ */
+export interface CargoDiagnosticsRun {
+    cwd: string;
+    /** Cargo workspace root, which rustc's file paths are relative to; falls back to cwd until cargo metadata has loaded */
+    workspaceRoot: string;
+    command: string;
+    messages: CargoCompilerMessage[];
+    artifacts: CargoCompilerArtifact[];
+    /** From the build-finished message, or the exit code when cargo stopped before sending one */
+    success: boolean;
+}
+
+const runUpdatedEmitter = new vscode.EventEmitter<CargoDiagnosticsRun>();
+const runFinishedEmitter = new vscode.EventEmitter<CargoDiagnosticsRun>();
+
+/**
+ * Fires when a command starts and whenever it reports a new compiler message, for live counts.
+ */
+export const onDidUpdateDiagnosticsRun = runUpdatedEmitter.event;
+
+/**
+ * Fires after every command run through runCargoWithDiagnostics exits.
+ */
+export const onDidFinishDiagnosticsRun = runFinishedEmitter.event;
+
+/**
+ * Checks whether `cargui.jsonDiagnostics` is on.
+ */
+export function isJsonDiagnosticsEnabled(): boolean {
+    return vscode.workspace.getConfiguration('cargui').get<boolean>('jsonDiagnostics', false);
+}
+
+/**
+ * Adds `--message-format=json-diagnostic-rendered-ansi` right after the cargo subcommand, so it stays in front of
+ * any `--` program or test arguments.
+ *
+ * @param command - Full cargo command line, optionally prefixed with `KEY=VALUE` environment variables
+ * @returns The rewritten command, or undefined if the subcommand doesn't support JSON output or a format is already set
+ */
+export function withJsonMessageFormat(command: string): string | undefined {
+    const match = matchCargoSubcommand(command);
+    if (!match || !DIAGNOSTIC_ACTIONS.includes(match[3]) || command.includes('--message-format')) {
+        return undefined;
+    }
+    const insertAt = (match.index ?? 0) + match[0].length;
+    return `${command.slice(0, insertAt)} ${MESSAGE_FORMAT_FLAG}${command.slice(insertAt)}`;
+}
+
+/**
+ * Finds the cargo subcommand in a command line, skipping environment prefixes and a `+toolchain` argument.
+ *
+ * @param command - Full cargo command line
+ * @returns The subcommand (e.g. "build"), or undefined if the line doesn't run cargo
+ */
+export function getCargoSubcommand(command: string): string | undefined {
+    return matchCargoSubcommand(command)?.[3];
+}
+
+function matchCargoSubcommand(command: string): RegExpMatchArray | null {
+    return command.match(/(^|\s)cargo(\s+\+\S+)?\s+([\w-]+)/);
+}
+
+/**
+ * Parses one line of cargo's JSON output.
+ *
+ * @param line - A line from cargo's stdout
+ * @returns The message; null for cargo messages cargUI ignores (e.g. build-script-executed); undefined for lines
+ * that aren't cargo messages at all, such as test output (which may itself be JSON)
+ */
+export function parseCargoMessage(line: string): CargoMessage | null | undefined {
+    const trimmed = line.trim();
+    if (!trimmed.startsWith('{')) {
+        return undefined;
+    }
+    let parsed: any;
+    try {
+        parsed = JSON.parse(trimmed);
+    } catch (error) {
+        return undefined;
+    }
+    if (!parsed || typeof parsed.reason !== 'string') {
+        return undefined;
+    }
+    switch (parsed.reason) {
+        case 'compiler-message':
+            return parsed.message ? parsed as CargoCompilerMessage : null;
+        case 'compiler-artifact':
+            return { ...parsed, fresh: !!parsed.fresh, filenames: parsed.filenames ?? [] } as CargoCompilerArtifact;
+        case 'build-finished':
+            return { reason: 'build-finished', success: !!parsed.success };
+        default:
+            return null;
+    }
+}
+
+/**
+ * Converts the compiler messages of one or more runs into VS Code diagnostics grouped by file.
+ * Messages without a source location (e.g. "aborting due to 2 previous errors") are skipped, and a message
+ * reported once per target (lib, bin, tests) or by several runs is only kept once. Notes and help lines are appended to the message.
+ *
+ * @param runs - Runs whose messages to merge
+ * @returns Diagnostics keyed by absolute file path
+ */
+export function toVsCodeDiagnostics(runs: CargoDiagnosticsRun[]): Map<string, vscode.Diagnostic[]> {
+    const byFile = new Map<string, vscode.Diagnostic[]>();
+    const seen = new Set<string>();
+
+    for (const run of runs) {
+        for (const { message } of run.messages) {
+            addDiagnostic(byFile, seen, run.workspaceRoot, message);
+        }
+    }
+
+    return byFile;
+}
+
+function addDiagnostic(byFile: Map<string, vscode.Diagnostic[]>, seen: Set<string>, workspaceRoot: string, message: CargoDiagnostic) {
+    const span = message.spans.find(candidate => candidate.is_primary);
+    if (!span) {
+        return;
+    }
+    // rustc paths are relative to the cargo workspace root, which differs from cwd when a member is opened on its own
+    const filePath = path.resolve(workspaceRoot, span.file_name);
+    const key = `${filePath}:${span.line_start}:${span.column_start}:${message.level}:${message.message}`;
+    if (seen.has(key)) {
+        return;
+    }
+    seen.add(key);
+
+    const range = new vscode.Range(span.line_start - 1, span.column_start - 1, span.line_end - 1, span.column_end - 1);
+    let text = message.message;
+    if (span.label) {
+        text += `\n${span.label}`;
+    }
+    for (const child of message.children) {
+        text += `\n${child.level}: ${child.message}`;
+    }
+
+    const diagnostic = new vscode.Diagnostic(range, text, getDiagnosticSeverity(message.level));
+    const code = message.code?.code;
+    if (code) {
+        diagnostic.code = code;
+    }
+    diagnostic.source = code?.startsWith('clippy::') ? 'clippy' : 'rustc';
+
+    const diagnostics = byFile.get(filePath) ?? [];
+    diagnostics.push(diagnostic);
+    byFile.set(filePath, diagnostics);
+}
+
+/**
+ * Counts the errors and warnings that would show in the Problems panel.
+ *
+ * @param diagnostics - Diagnostics returned by toVsCodeDiagnostics
+ * @returns Error and warning counts; notes and help messages aren't counted
+ */
+export function countDiagnostics(diagnostics: Map<string, vscode.Diagnostic[]>): { errors: number; warnings: number } {
+    let errors = 0;
+    let warnings = 0;
+    for (const fileDiagnostics of diagnostics.values()) {
+        for (const diagnostic of fileDiagnostics) {
+            if (diagnostic.severity === vscode.DiagnosticSeverity.Error) {
+                errors++;
+            } else if (diagnostic.severity === vscode.DiagnosticSeverity.Warning) {
+                warnings++;
+            }
+        }
+    }
+    return { errors, warnings };
+}
+
+function getDiagnosticSeverity(level: string): vscode.DiagnosticSeverity {
+    if (level.startsWith('error')) {
+        return vscode.DiagnosticSeverity.Error;
+    }
+    if (level === 'warning') {
+        return vscode.DiagnosticSeverity.Warning;
+    }
+    if (level === 'note') {
+        return vscode.DiagnosticSeverity.Information;
+    }
+    return vscode.DiagnosticSeverity.Hint;
+}
+
+/**
+ * Runs a cargo command with JSON message output in a terminal driven by cargUI.
+ * Compiler messages are shown as rustc renders them, cargo's own progress lines on stderr pass through,
+ * and stdout lines that aren't cargo JSON (test output) are printed unchanged. Ctrl+C stops cargo.
+ *
+ * @param name - Terminal name
+ * @param cwd - Directory to run the command in
+ * @param command - Command line returned by withJsonMessageFormat
+ * @param env - Variables to set on top of the inherited environment
+ * @returns The terminal the command runs in
+ */
+export function runCargoWithDiagnostics(name: string, cwd: string, command: string, env: Record<string, string>): vscode.Terminal {
+    const writeEmitter = new vscode.EventEmitter<string>();
+    const closeEmitter = new vscode.EventEmitter<number | void>();
+    const write = (text: string) => writeEmitter.fire(text.replace(/\r?\n/g, '\r\n'));
+    let child: any;
+    let running = false;
+
+    const stop = () => {
+        if (!running) {
+            return;
+        }
+        try {
+            // The command runs through a shell, so signal the whole process group to reach cargo and rustc too
+            if (process.platform === 'win32') {
+                child.kill();
+            } else {
+                process.kill(-child.pid, 'SIGINT');
+            }
+        } catch (error) {
+            // Already exited
+        }
+    };
+
+    const pty: vscode.Pseudoterminal = {
+        onDidWrite: writeEmitter.event,
+        onDidClose: closeEmitter.event,
+        open: () => {
+            const { spawn } = require('child_process');
+            write(`\x1b[2m${command}\x1b[0m\n`);
+            child = spawn(command, {
+                cwd,
+                shell: true,
+                detached: process.platform !== 'win32',
+                env: { ...process.env, ...env, CARGO_TERM_COLOR: 'always' }
+            });
+            running = true;
+
+            const run: CargoDiagnosticsRun = {
+                cwd,
+                workspaceRoot: getCachedCargoMetadata(cwd)?.workspace_root ?? cwd,
+                command,
+                messages: [],
+                artifacts: [],
+                success: false
+            };
+            const workspaceRootLoaded = getCargoMetadata(cwd).then(metadata => {
+                if (metadata) {
+                    run.workspaceRoot = metadata.workspace_root;
+                }
+            });
+            let finished: boolean | undefined;
+            let pending = '';
+            // Lets listeners track the run from the start, before any message arrives
+            runUpdatedEmitter.fire(run);
+
+            const handleLine = (line: string) => {
+                const message = parseCargoMessage(line);
+                if (message === undefined) {
+                    // Test harness output and anything else that isn't a cargo message, including JSON printed by tests
+                    write(`${line}\n`);
+                    return;
+                }
+                if (message === null) {
+                    return;
+                }
+                if (message.reason === 'compiler-message') {
+                    run.messages.push(message);
+                    if (message.message.rendered) {
+                        write(message.message.rendered);
+                    }
+                    runUpdatedEmitter.fire(run);
+                } else if (message.reason === 'compiler-artifact') {
+                    run.artifacts.push(message);
+                } else {
+                    finished = message.success;
+                }
+            };
+
+            child.stdout.on('data', (data: Buffer) => {
+                pending += data.toString();
+                const lines = pending.split('\n');
+                pending = lines.pop() ?? '';
+                lines.forEach(handleLine);
+            });
+            child.stderr.on('data', (data: Buffer) => write(data.toString()));
+            child.on('error', (error: Error) => write(`\x1b[31m${error.message}\x1b[0m\n`));
+            child.on('close', (code: number | null) => {
+                running = false;
+                if (pending) {
+                    handleLine(pending);
+                }
+                run.success = finished ?? code === 0;
+                write(`\n\x1b[2mcargo exited with code ${code ?? 'unknown'}. Close this terminal or press any key.\x1b[0m\n`);
+                // Wait for the workspace root so the final diagnostics point at the right files
+                workspaceRootLoaded.then(() => runFinishedEmitter.fire(run));
+            });
+        },
+        close: stop,
+        handleInput: (data: string) => {
+            if (running) {
+                if (data === '\x03') {
+                    stop();
+                }
+                return;
+            }
+            closeEmitter.fire();
+        }
+    };
+
+    const terminal = vscode.window.createTerminal({ name, pty });
+    terminal.show();
+    return terminal;
+}
@@ -94,6 +94,40 @@ export function discoverWorkspaceMembers(workspacePath: string): WorkspaceMember
     return members;
 }
 
+/**
+ * Finds the root of an enclosing Cargo workspace when a member package is opened on its own.
+ * Walks parent directories looking for a Cargo.toml with a [workspace] table that lists the package as a member.
+ * 
+ * @param packagePath - Absolute path of the opened package folder
+ * @returns Absolute path of the workspace root, or undefined if the package is not a workspace member
+ */
+export function findParentWorkspaceRoot(packagePath: string): string | undefined {
+    const resolvedPackagePath = path.resolve(packagePath);
+    let currentDir = path.dirname(resolvedPackagePath);
+
+    while (currentDir !== path.dirname(currentDir)) {
+        const cargoTomlPath = path.join(currentDir, 'Cargo.toml');
+        if (fs.existsSync(cargoTomlPath)) {
+            try {
+                const manifest = toml.parse(fs.readFileSync(cargoTomlPath, 'utf-8')) as CargoManifest;
+                if (manifest.workspace) {
+                    const members = discoverWorkspaceMembers(currentDir);
+                    if (members.some(m => path.resolve(currentDir, m.path) === resolvedPackagePath)) {
+                        return currentDir;
+                    }
+                    // Cargo doesn't nest workspaces, so the first [workspace] we hit is the only candidate
+                    return undefined;
+                }
+            } catch (error) {
+                console.error(`Error parsing Cargo.toml at ${cargoTomlPath}:`, error);
+            }
+        }
+        currentDir = path.dirname(currentDir);
+    }
+
+    return undefined;
+}
+
 /**
  * Discovers all cargo targets (binaries, examples, tests, benches) from Cargo.toml and filesystem.
  * Combines explicit targets from manifest with auto-discovered targets from standard directories.
@@ -0,0 +1,238 @@
+import * as path from 'path';
+import * as fs from 'fs';
+import * as toml from '@iarna/toml';
+import { CargoMetadata, CargoMetadataPackage, CargoMetadataTarget, CargoTarget, WorkspaceMember } from './types';
+import { discoverWorkspaceMembers, discoverCargoTargets, discoverCargoFeatures } from './cargoDiscovery';
+
+/**
+ * Cached metadata per workspace root. Entries hold the in-flight promise so concurrent callers share one cargo run.
+ */
+const metadataCache = new Map<string, Promise<CargoMetadata | undefined>>();
+
+/**
+ * Last metadata that loaded successfully, for synchronous callers that can fall back when it isn't there yet.
+ */
+const resolvedMetadata = new Map<string, CargoMetadata>();
+
+/**
+ * Runs `cargo metadata` for a package or workspace and caches the result until it is invalidated.
+ * Only workspace packages are included (`--no-deps`), which keeps the call fast and offline.
+ * 
+ * @param workspacePath - Package or workspace root
+ * @returns Promise resolving to the metadata, or undefined if cargo metadata failed (e.g. invalid Cargo.toml)
+ */
+export function getCargoMetadata(workspacePath: string): Promise<CargoMetadata | undefined> {
+    const cached = metadataCache.get(workspacePath);
+    if (cached) {
+        return cached;
+    }
+
+    const pending = runCargoMetadata(workspacePath);
+    metadataCache.set(workspacePath, pending);
+    // Don't cache failures, so fixing Cargo.toml is picked up on the next call even without a watcher event
+    pending.then(metadata => {
+        if (metadataCache.get(workspacePath) !== pending) {
+            return;
+        }
+        if (metadata) {
+            resolvedMetadata.set(workspacePath, metadata);
+        } else {
+            metadataCache.delete(workspacePath);
+        }
+    });
+    return pending;
+}
+
+/**
+ * Drops the cached cargo run so the next call to getCargoMetadata runs cargo again.
+ * The last successfully loaded metadata stays available to getCachedCargoMetadata until a newer run succeeds,
+ * so a half-edited Cargo.toml doesn't blank out the target directory or package details.
+ * 
+ * @param workspacePath - Workspace root to invalidate; clears every entry when omitted
+ */
+export function invalidateCargoMetadata(workspacePath?: string) {
+    if (workspacePath) {
+        metadataCache.delete(workspacePath);
+    } else {
+        metadataCache.clear();
+    }
+}
+
+/**
+ * Returns metadata that has already been loaded, without running cargo.
+ * 
+ * @param workspacePath - Package or workspace root
+ * @returns The last successfully loaded metadata (possibly from before the last invalidation), or undefined if none has loaded yet
+ */
+export function getCachedCargoMetadata(workspacePath: string): CargoMetadata | undefined {
+    return resolvedMetadata.get(workspacePath);
+}
+
+/**
+ * Finds a workspace package by name in cached metadata.
+ * 
+ * @param metadata - Metadata returned by getCargoMetadata
+ * @param packageName - Package name from Cargo.toml
+ * @returns The package, or undefined if it isn't part of the workspace
+ */
+export function findMetadataPackage(metadata: CargoMetadata, packageName: string): CargoMetadataPackage | undefined {
+    return metadata.packages.find(pkg => pkg.name === packageName);
+}
+
+/**
+ * Finds the package whose manifest is `<packagePath>/Cargo.toml`, i.e. the root package of a non-virtual manifest.
+ * 
+ * @param metadata - Metadata returned by getCargoMetadata
+ * @param packagePath - Directory containing the package's Cargo.toml
+ * @returns The package, or undefined for virtual manifests
+ */
+export function findMetadataPackageAt(metadata: CargoMetadata, packagePath: string): CargoMetadataPackage | undefined {
+    const manifestPath = path.join(packagePath, 'Cargo.toml');
+    return metadata.packages.find(pkg => path.resolve(pkg.manifest_path) === path.resolve(manifestPath));
+}
+
+/**
+ * Lists workspace members as cargo resolves them, so globs, `exclude` and path members match what cargo builds.
+ * Falls back to parsing Cargo.toml while metadata hasn't loaded (or failed to load) for this path.
+ * 
+ * @param workspacePath - Workspace root
+ * @returns Members with paths relative to the workspace root; empty for a single package or a member opened on its own
+ */
+export function getWorkspaceMembers(workspacePath: string): WorkspaceMember[] {
+    const metadata = getCachedCargoMetadata(workspacePath);
+    if (!metadata) {
+        return discoverWorkspaceMembers(workspacePath);
+    }
+    // A member opened on its own reports the enclosing workspace, which cargUI treats as a single package
+    if (path.resolve(metadata.workspace_root) !== path.resolve(workspacePath)) {
+        return [];
+    }
+
+    const members = metadata.packages.map(pkg => {
+        const memberPath = path.relative(workspacePath, path.dirname(pkg.manifest_path)).split(path.sep).join('/') || '.';
+        return { name: pkg.name, path: memberPath, isRoot: memberPath === '.' };
+    });
+    // A lone root package isn't a workspace
+    if (members.length === 1 && members[0].isRoot) {
+        return [];
+    }
+    return members.sort((a, b) => Number(b.isRoot) - Number(a.isRoot));
+}
+
+/**
+ * Lists a package's targets as cargo resolves them, including the ones cargo auto-discovers.
+ * Targets Cargo.toml doesn't declare keep the autoDiscovered flag from cargoDiscovery's filesystem scan,
+ * and scanned files cargo doesn't build (e.g. with `autoexamples = false`) are still listed so they can be registered.
+ * Falls back to parsing Cargo.toml while metadata hasn't loaded for this path.
+ * 
+ * @param workspacePath - Workspace root
+ * @param memberPath - Member path relative to the workspace root, or undefined for the root package
+ * @returns The package's targets with paths relative to the package folder
+ */
+export function getCargoTargets(workspacePath: string, memberPath?: string): CargoTarget[] {
+    const scanned = discoverCargoTargets(workspacePath, memberPath);
+    const metadata = getCachedCargoMetadata(workspacePath);
+    const basePath = memberPath ? path.join(workspacePath, memberPath) : workspacePath;
+    const pkg = metadata ? findMetadataPackageAt(metadata, basePath) : undefined;
+    if (!pkg) {
+        return scanned;
+    }
+
+    const targets: CargoTarget[] = [];
+    for (const target of pkg.targets) {
+        const type = getTargetType(target);
+        if (!type) {
+            continue;
+        }
+        // cargo reports the lib under its crate name (underscores); cargUI names it after the package like Cargo.toml does
+        const name = type === 'lib' && target.name === pkg.name.replace(/-/g, '_') ? pkg.name : target.name;
+        const targetPath = path.relative(basePath, target.src_path).split(path.sep).join('/');
+        const scannedTarget = scanned.find(t => t.type === type && (t.name === name || t.path === targetPath));
+        targets.push({
+            name,
+            type,
+            path: targetPath,
+            requiredFeatures: target.required_features,
+            autoDiscovered: scannedTarget?.autoDiscovered
+        });
+    }
+    for (const target of scanned) {
+        if (target.autoDiscovered && !targets.some(t => t.name === target.name && t.type === target.type)) {
+            targets.push(target);
+        }
+    }
+    return targets;
+}
+
+/**
+ * Lists the features of a package as cargo resolves them (including implicit features of optional dependencies).
+ * Falls back to parsing Cargo.toml while metadata hasn't loaded for this path.
+ * 
+ * @param workspacePath - Workspace root
+ * @param memberPath - Member path relative to the workspace root, or undefined for the root package
+ * @returns Feature names, sorted as cargo reports them
+ */
+export function getCargoFeatures(workspacePath: string, memberPath?: string): string[] {
+    const metadata = getCachedCargoMetadata(workspacePath);
+    const basePath = memberPath ? path.join(workspacePath, memberPath) : workspacePath;
+    const pkg = metadata ? findMetadataPackageAt(metadata, basePath) : undefined;
+    return pkg ? Object.keys(pkg.features) : discoverCargoFeatures(workspacePath, memberPath);
+}
+
+/**
+ * Maps cargo metadata target kinds to cargUI target types; build scripts are skipped.
+ */
+function getTargetType(target: CargoMetadataTarget): CargoTarget['type'] | undefined {
+    for (const type of ['bin', 'example', 'test', 'bench'] as const) {
+        if (target.kind.includes(type)) {
+            return type;
+        }
+    }
+    // lib, rlib, dylib, cdylib, staticlib and proc-macro are all the package's library
+    return target.kind.some(kind => kind.endsWith('lib') || kind === 'proc-macro') ? 'lib' : undefined;
+}
+
+function runCargoMetadata(workspacePath: string): Promise<CargoMetadata | undefined> {
+    return new Promise((resolve) => {
+        const { exec } = require('child_process');
+        exec('cargo metadata --no-deps --format-version 1', { cwd: workspacePath, maxBuffer: 1024 * 1024 * 10 }, (error: any, stdout: string, stderr: string) => {
+            if (error) {
+                console.error('[cargUI] cargo metadata failed:', stderr || error);
+                resolve(undefined);
+                return;
+            }
+            try {
+                const raw = JSON.parse(stdout);
+                resolve({
+                    packages: raw.packages ?? [],
+                    workspace_members: raw.workspace_members ?? [],
+                    workspace_default_members: raw.workspace_default_members,
+                    workspace_root: raw.workspace_root ?? workspacePath,
+                    target_directory: raw.target_directory ?? path.join(workspacePath, 'target'),
+                    profiles: readDeclaredProfiles(raw.workspace_root ?? workspacePath)
+                });
+            } catch (parseError) {
+                console.error('[cargUI] Failed to parse cargo metadata:', parseError);
+                resolve(undefined);
+            }
+        });
+    });
+}
+
+/**
+ * Lists the built-in profiles plus any custom `[profile.<name>]` tables in the workspace root Cargo.toml.
+ */
+function readDeclaredProfiles(workspaceRoot: string): string[] {
+    const profiles = ['dev', 'release', 'test', 'bench'];
+    try {
+        const manifest = toml.parse(fs.readFileSync(path.join(workspaceRoot, 'Cargo.toml'), 'utf-8')) as any;
+        for (const name of Object.keys(manifest.profile ?? {})) {
+            if (!profiles.includes(name)) {
+                profiles.push(name);
+            }
+        }
+    } catch (error) {
+        console.error('[cargUI] Failed to read profiles from Cargo.toml:', error);
+    }
+    return profiles;
+}
@@ -12,16 +12,21 @@ import {
     CustomCommandCategory,
     ArgumentCategory,
     UnregisteredItem,
-    ModuleInfo
+    ModuleInfo,
+    CargoMetadata
 } from './types';
 import { CargoTreeItem } from './treeItems';
 import { DependencyDecorationProvider } from './decorationProvider';
 import {
-    discoverWorkspaceMembers,
-    discoverCargoTargets,
-    discoverCargoFeatures,
-    discoverCargoDependencies
-} from './cargoDiscovery';
+    getCargoMetadata,
+    getCachedCargoMetadata,
+    findMetadataPackage,
+    findMetadataPackageAt,
+    getWorkspaceMembers,
+    getCargoTargets,
+    getCargoFeatures
+} from './cargoMetadata';
+import { discoverCargoDependencies } from './cargoDiscovery';
 import { detectModules, buildModuleTree } from './moduleDetection';
 import { fetchCrateVersions } from './cratesIo';
 import { CargoTreeState } from './cargoCommands';
@@ -52,6 +57,7 @@ export class CargoTreeDataProvider implements
     private context?: vscode.ExtensionContext;
     public decorationProvider?: DependencyDecorationProvider;
     public treeView?: vscode.TreeView<CargoTreeItem>;
+    private cargoMetadata?: CargoMetadata;
 
     // Drag and drop support
     dropMimeTypes = ['application/vnd.code.tree.cargoTreeView'];
@@ -110,10 +116,59 @@ export class CargoTreeDataProvider implements
             this.decorationProvider.refresh();
         }
         this._onDidChangeTreeData.fire();
+        this.updateViewDescription();
+        this.loadCargoMetadata();
         // Run smart detection after refresh (debounced)
         this.triggerSmartDetection();
     }
 
+    /**
+     * Loads cargo metadata in the background and redraws once it arrives, so the header can show
+     * values only cargo resolves (e.g. a rust-version inherited from the workspace).
+     */
+    private loadCargoMetadata(): void {
+        const workspaceFolder = this.getWorkspaceFolder();
+        if (!workspaceFolder) {
+            return;
+        }
+        getCargoMetadata(workspaceFolder.uri.fsPath).then(loaded => {
+            // Keep showing the last good metadata while Cargo.toml doesn't parse
+            const metadata = loaded ?? getCachedCargoMetadata(workspaceFolder.uri.fsPath);
+            // Cached metadata resolves to the same object, so this only redraws when something changed
+            if (metadata !== this.cargoMetadata) {
+                this.cargoMetadata = metadata;
+                this._onDidChangeTreeData.fire();
+            }
+        });
+    }
+
+    /**
+     * Shows the package name (or the folder name for virtual workspaces) next to the view title,
+     * so it's clear which package the buttons act on in multi-root windows.
+     */
+    private updateViewDescription(): void {
+        if (!this.treeView) {
+            return;
+        }
+        const workspaceFolder = this.getWorkspaceFolder();
+        if (!workspaceFolder) {
+            this.treeView.description = undefined;
+            return;
+        }
+
+        let name = workspaceFolder.name;
+        try {
+            const cargoTomlPath = path.join(workspaceFolder.uri.fsPath, 'Cargo.toml');
+            const manifest = toml.parse(fs.readFileSync(cargoTomlPath, 'utf-8')) as CargoManifest;
+            if (manifest.package?.name) {
+                name = manifest.package.name;
+            }
+        } catch (error) {
+            // Keep the folder name when Cargo.toml is missing or mid-edit
+        }
+        this.treeView.description = name;
+    }
+
     private detectionTimeout?: NodeJS.Timeout;
     
     private triggerSmartDetection(): void {
@@ -128,7 +183,7 @@ export class CargoTreeDataProvider implements
             }
             
             // we detect both unknown targets and undeclared features
-            const workspaceMembers = discoverWorkspaceMembers(this.workspaceFolder.uri.fsPath);
+            const workspaceMembers = getWorkspaceMembers(this.workspaceFolder.uri.fsPath);
             const targetMemberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
                 ? workspaceMembers.find(m => m.name === this.selectedWorkspaceMember)?.path
                 : undefined;
@@ -137,7 +192,7 @@ export class CargoTreeDataProvider implements
             
             // Detect undeclared modules
             let undeclaredModules: ModuleInfo[] = [];
-            const members = discoverWorkspaceMembers(this.workspaceFolder.uri.fsPath);
+            const members = getWorkspaceMembers(this.workspaceFolder.uri.fsPath);
             if (members.length > 1) {
                 for (const member of members) {
                     const srcPath = path.join(this.workspaceFolder.uri.fsPath, member.path, 'src');
@@ -401,7 +456,7 @@ export class CargoTreeDataProvider implements
             return;
         }
 
-        const workspaceMembers = discoverWorkspaceMembers(this.workspaceFolder.uri.fsPath);
+        const workspaceMembers = getWorkspaceMembers(this.workspaceFolder.uri.fsPath);
         let member = item.memberName 
             ? workspaceMembers.find(m => m.name === item.memberName)
             : undefined;
@@ -603,7 +658,7 @@ export class CargoTreeDataProvider implements
 
         // Move the file if requested
         if (shouldMove) {
-            const workspaceMembers = discoverWorkspaceMembers(this.workspaceFolder.uri.fsPath);
+            const workspaceMembers = getWorkspaceMembers(this.workspaceFolder.uri.fsPath);
             let member = unknown.memberName 
                 ? workspaceMembers.find(m => m.name === unknown.memberName)
                 : undefined;
@@ -709,7 +764,7 @@ export class CargoTreeDataProvider implements
 
     private getMemberNameAndVersion(workspacePath: string, memberName: string): { name: string; version: string } | null {
         // We read the member's name and version from their Cargo.toml
-        const workspaceMembers = discoverWorkspaceMembers(workspacePath);
+        const workspaceMembers = getWorkspaceMembers(workspacePath);
         const member = workspaceMembers.find(m => m.name === memberName);
         
         if (!member) {
@@ -782,13 +837,28 @@ export class CargoTreeDataProvider implements
                 console.log('[cargUI] NOT entering member info block - selectedWorkspaceMember:', this.selectedWorkspaceMember);
             }
 
+            // Edition and rust-version come from cargo metadata, which resolves workspace inheritance
+            let headerPackageDescription = '';
+            if (this.cargoMetadata) {
+                const headerPackage = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
+                    ? findMetadataPackage(this.cargoMetadata, this.selectedWorkspaceMember)
+                    : findMetadataPackageAt(this.cargoMetadata, workspaceFolder.uri.fsPath);
+                if (headerPackage) {
+                    tooltipText += `\nEdition: ${headerPackage.edition}`;
+                    if (headerPackage.rust_version) {
+                        tooltipText += `\nRust version: ${headerPackage.rust_version}`;
+                        headerPackageDescription = `rust ${headerPackage.rust_version}`;
+                    }
+                }
+            }
+
             const projectItem = new CargoTreeItem(
                 headerLabel,
                 vscode.TreeItemCollapsibleState.None,
                 TreeItemContext.ProjectHeader,
                 { iconName: 'package' }
             );
-            projectItem.description = '';
+            projectItem.description = headerPackageDescription;
             projectItem.tooltip = tooltipText;
             
             // we set a VS Code context variable to track if a member is selected
@@ -827,7 +897,7 @@ export class CargoTreeDataProvider implements
             
             if (this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all') {
                 // Get the member's edition
-                const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+                const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
                 const member = members.find(m => m.name === this.selectedWorkspaceMember);
                 if (member) {
                     const memberPath = path.join(workspaceFolder.uri.fsPath, member.path);
@@ -879,7 +949,7 @@ export class CargoTreeDataProvider implements
             }
 
             // Workspace Members (only show if multi-crate workspace)
-            const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+            const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
             const isWorkspace = workspaceMembers.length > 0;
             
             // Set context for conditional UI elements
@@ -920,7 +990,7 @@ export class CargoTreeDataProvider implements
             // Count total modules and undeclared modules
             let totalModules = 0;
             let totalUndeclared = 0;
-            const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+            const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
             if (members.length > 1) {
                 // Multi-member workspace - count modules per member
                 for (const member of members) {
@@ -990,12 +1060,22 @@ export class CargoTreeDataProvider implements
             items.push(snapshotsItem);
 
             // Mode indicator (moved under snapshots since it's snapshottable)
-            items.push(new CargoTreeItem(
-                this.isReleaseMode ? 'Mode: Release' : 'Mode: Debug',
+            // A custom profile from cargui.customProfile replaces the Debug/Release mode
+            const customProfile = vscode.workspace.getConfiguration('cargui').get<string>('customProfile', '').trim();
+            const modeItem = new CargoTreeItem(
+                customProfile ? `Profile: ${customProfile}` : (this.isReleaseMode ? 'Mode: Release' : 'Mode: Debug'),
                 vscode.TreeItemCollapsibleState.None,
                 TreeItemContext.Mode,
-                { iconName: this.isReleaseMode ? 'rocket' : 'bug' }
-            ));
+                { iconName: customProfile ? 'settings-gear' : (this.isReleaseMode ? 'rocket' : 'bug') }
+            );
+            // we show the cross-compilation target and toolchain next to the mode, since they change every build
+            const targetTriple = vscode.workspace.getConfiguration('cargui').get<string>('targetTriple', '').trim();
+            const toolchain = vscode.workspace.getConfiguration('cargui').get<string>('toolchain', '').trim();
+            const modeDetails = [toolchain ? `+${toolchain}` : '', targetTriple].filter(detail => detail);
+            if (modeDetails.length > 0) {
+                modeItem.description = modeDetails.join(' ');
+            }
+            items.push(modeItem);
 
             // Targets
             const targetMemberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all' 
@@ -1003,7 +1083,7 @@ export class CargoTreeDataProvider implements
                 : undefined;
             const targets = this.selectedWorkspaceMember === 'all' 
                 ? [] 
-                : discoverCargoTargets(workspaceFolder.uri.fsPath, targetMemberPath);
+                : getCargoTargets(workspaceFolder.uri.fsPath, targetMemberPath);
             const unknownTargets = this.selectedWorkspaceMember === 'all'
                 ? []
                 : this.detectUnregisteredTargetsFunc(workspaceFolder.uri.fsPath, targetMemberPath);
@@ -1043,7 +1123,7 @@ export class CargoTreeDataProvider implements
             // Don't show features when no member selected or 'all' selected - features are member-specific
             const features = (this.selectedWorkspaceMember === 'all' || !this.selectedWorkspaceMember)
                 ? []
-                : discoverCargoFeatures(workspaceFolder.uri.fsPath, featureMemberPath);
+                : getCargoFeatures(workspaceFolder.uri.fsPath, featureMemberPath);
             const undeclaredFeatures = (this.selectedWorkspaceMember === 'all' || !this.selectedWorkspaceMember)
                 ? []
                 : detectUndeclaredFeatures(workspaceFolder.uri.fsPath, featureMemberPath);
@@ -1057,6 +1137,13 @@ export class CargoTreeDataProvider implements
                     
                 const checkedCount = features.filter(f => this.checkedFeatures.has(f)).length + undeclaredFeatures.filter(f => this.checkedFeatures.has(f.name)).length;
                 featuresItem.description = `${totalFeatures}${checkedCount > 0 ? ` ✓${checkedCount}` : ''}`;
+                // we show the default-features mode next to the count when it isn't cargo's default
+                const featureMode = vscode.workspace.getConfiguration('cargui').get<string>('featureMode', 'default');
+                if (featureMode === 'allFeatures') {
+                    featuresItem.description += ' · all features';
+                } else if (featureMode === 'noDefaultFeatures') {
+                    featuresItem.description += ' · no default';
+                }
                 if (checkedCount > 0) {
                     const checkedFeatures = features.filter(f => this.checkedFeatures.has(f));
                     const checkedUndeclared = undeclaredFeatures.filter(f => this.checkedFeatures.has(f.name));
@@ -1130,7 +1217,7 @@ export class CargoTreeDataProvider implements
             return Promise.resolve(items);
         } else if (element.contextValue === TreeItemContext.WorkspaceCategory) {
             // Workspace category children - show all members
-            const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+            const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
             const items: CargoTreeItem[] = [];
             
             // Add individual members (removed "All Members" item - use Check All button instead)
@@ -1193,7 +1280,7 @@ export class CargoTreeDataProvider implements
             
             if (workspaceFolder) {
                 // Check if it's a workspace with members
-                const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+                const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
                 
                 if (members.length > 1) {
                     // Multi-member workspace
@@ -1302,6 +1389,12 @@ export class CargoTreeDataProvider implements
             } else {
                 tooltipText = `Mode: ${snapshot.mode}`;
             }
+            if (snapshot.customProfile) {
+                tooltipText += `\nProfile: ${snapshot.customProfile}`;
+            }
+            if (snapshot.targetTriple) {
+                tooltipText += `\nTarget: ${snapshot.targetTriple}`;
+            }
             
             // Itemize targets
             if (snapshot.targets.length > 0) {
@@ -1556,11 +1649,11 @@ export class CargoTreeDataProvider implements
             }));
         } else if (element.contextValue === TreeItemContext.FeaturesCategory) {
             // Features category children
-            const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+            const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
             const featureMemberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
                 ? workspaceMembers.find(m => m.name === this.selectedWorkspaceMember)?.path
                 : undefined;
-            const features = discoverCargoFeatures(workspaceFolder.uri.fsPath, featureMemberPath);
+            const features = getCargoFeatures(workspaceFolder.uri.fsPath, featureMemberPath);
             const undeclaredFeatures = detectUndeclaredFeatures(workspaceFolder.uri.fsPath, featureMemberPath);
             
             const allFeatureItems = [
@@ -1632,7 +1725,7 @@ export class CargoTreeDataProvider implements
             // Dependencies category children - show subfolders (WORKSPACE/Production/Dev/Build)
             const items: CargoTreeItem[] = [];
             
-            const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+            const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
             const dependencyMemberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
                 ? workspaceMembers.find(m => m.name === this.selectedWorkspaceMember)?.path
                 : undefined;
@@ -1708,11 +1801,11 @@ export class CargoTreeDataProvider implements
             const items: CargoTreeItem[] = [];
             
             // Get all targets to count by type
-            const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+            const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
             const targetMemberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
                 ? workspaceMembers.find(m => m.name === this.selectedWorkspaceMember)?.path
                 : undefined;
-            const allTargets = discoverCargoTargets(workspaceFolder.uri.fsPath, targetMemberPath);
+            const allTargets = getCargoTargets(workspaceFolder.uri.fsPath, targetMemberPath);
             
             // Count targets by type
             const libCount = allTargets.filter(t => t.type === 'lib').length;
@@ -1808,11 +1901,11 @@ export class CargoTreeDataProvider implements
         } else if (element.contextValue === TreeItemContext.TargetTypeFolder) {
             // Show targets of a specific type
             const targetType = element.categoryName as 'lib' | 'bin' | 'example' | 'test' | 'bench';
-            const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+            const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
             const targetMemberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
                 ? workspaceMembers.find(m => m.name === this.selectedWorkspaceMember)?.path
                 : undefined;
-            const allTargets = discoverCargoTargets(workspaceFolder.uri.fsPath, targetMemberPath);
+            const allTargets = getCargoTargets(workspaceFolder.uri.fsPath, targetMemberPath);
             const targets = allTargets.filter(t => t.type === targetType);
             
             // Helper function to determine target status for color coding
@@ -2006,7 +2099,7 @@ export class CargoTreeDataProvider implements
                 
                 // We analyze target file for documentation health
                 const memberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
-                    ? discoverWorkspaceMembers(workspaceFolder.uri.fsPath).find(m => m.name === this.selectedWorkspaceMember)?.path
+                    ? getWorkspaceMembers(workspaceFolder.uri.fsPath).find(m => m.name === this.selectedWorkspaceMember)?.path
                     : undefined;
                 const basePath = memberPath ? path.join(workspaceFolder.uri.fsPath, memberPath) : workspaceFolder.uri.fsPath;
                 const targetFilePath = path.join(basePath, target.path || '');
@@ -2124,7 +2217,7 @@ export class CargoTreeDataProvider implements
         ) {
             // Show dependencies of a specific type
             const depType = element.categoryName as 'workspace' | 'production' | 'dev' | 'build';
-            const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+            const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
             const dependencyMemberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
                 ? workspaceMembers.find(m => m.name === this.selectedWorkspaceMember)?.path
                 : undefined;
@@ -2283,7 +2376,7 @@ export class CargoTreeDataProvider implements
             // we check if this is the undeclared features folder or unknowns folder based on resourceUri
             if (element.resourceUri?.scheme === 'cargui-target' && element.resourceUri.authority === 'undeclared-features-folder') {
                 // Show undeclared features
-                const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+                const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
                 const targetMemberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
                     ? workspaceMembers.find(m => m.name === this.selectedWorkspaceMember)?.path
                     : undefined;
@@ -2315,7 +2408,7 @@ export class CargoTreeDataProvider implements
                 }));
             } else {
                 // Show unregistered targets
-                const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+                const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
                 const targetMemberPath = this.selectedWorkspaceMember && this.selectedWorkspaceMember !== 'all'
                     ? workspaceMembers.find(m => m.name === this.selectedWorkspaceMember)?.path
                     : undefined;
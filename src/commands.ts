@@ -2,6 +2,7 @@
 import * as vscode from 'vscode';
 import * as path from 'path';
 import * as fs from 'fs';
+import * as os from 'os';
 import * as toml from '@iarna/toml';
 
 import { CargoTreeDataProvider } from './cargoTreeProvider';
@@ -18,12 +19,7 @@ import {
 	DetectionResult,
 	ModuleInfo
 } from './types';
-import {
-	discoverWorkspaceMembers,
-	discoverCargoTargets,
-	discoverCargoFeatures,
-	discoverCargoDependencies
-} from './cargoDiscovery';
+import { discoverCargoDependencies } from './cargoDiscovery';
 import {
 	moveTargetToStandardLocation,
 	updateDependencyVersions,
@@ -36,18 +32,49 @@ import {
 	fetchCrateVersions,
 	searchCrates
 } from './cratesIo';
-import { getCurrentEdition, selectEdition, updateEdition } from './rustEdition';
+import { getCurrentEdition, selectEdition, updateEdition, getAvailableEditions } from './rustEdition';
 import {
 	buildWithFeature,
 	runCargoCommand,
 	runCargoCommandOnTargets,
+	buildCargoCommandsOnTargets,
+	rerunLastCargoCommands,
 	runCargoTarget,
-	buildSingleTarget
+	buildSingleTarget,
+	sendCargoCommand,
+	getCargoTerminalEnv,
+	expandHomePath,
+	collectClippyFixes,
+	ClippyFixSummary,
+	getTargetDirectory,
+	getProfileOutputDir,
+	getTargetOutputRoot,
+	getSettingsCargoFlags,
+	getProfileFlag,
+	quoteShellArgument,
+	appendCommandArguments,
+	describeCargoInvocation,
+	collectArtifactSizes,
+	collectBuiltArtifactSizes,
+	ArtifactSize,
+	formatBytes
 } from './cargoCommands';
 import {
 	getCurrentToolchain,
-	checkRustupUpdates
+	checkRustupUpdates,
+	listCargoSubcommands,
+	setToolchainOverride,
+	listInstalledTargets,
+	listInstalledToolchains
 } from './rustup';
+import {
+	getCargoMetadata,
+	invalidateCargoMetadata,
+	getWorkspaceMembers,
+	getCargoTargets,
+	getCargoFeatures
+} from './cargoMetadata';
+import { onDidFinishDiagnosticsRun, getCargoSubcommand } from './cargoDiagnostics';
 
 export interface CommandDependencies {
 	context: vscode.ExtensionContext;
@@ -140,6 +167,10 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		return disposable;
 	};
 
+	// Shared output channel for reports that are too long for a notification
+	const outputChannel = vscode.window.createOutputChannel('cargUI');
+	disposables.push(outputChannel);
+
 	// Helper function to auto-format Cargo.toml after edits with undo option
 	const autoFormatCargoToml = async (cargoTomlPath: string, memberName?: string, actionDescription: string = 'Cargo.toml modified') => {
 		const config = vscode.workspace.getConfiguration('cargui');
@@ -370,7 +401,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		}
 
 		if (!dependencyType) {
-			const workspaceMembers = discoverWorkspaceMembers(activeWorkspace.uri.fsPath);
+			const workspaceMembers = getWorkspaceMembers(activeWorkspace.uri.fsPath);
 			const isWorkspace = workspaceMembers.length > 0;
 
 			const typeOptions: Array<{ label: string; value: 'production' | 'dev' | 'build' | 'workspace'; description: string }> = [
@@ -402,7 +433,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			workspace: 'workspace.dependencies'
 		};
 
-		const workspaceMembers = discoverWorkspaceMembers(activeWorkspace.uri.fsPath);
+		const workspaceMembers = getWorkspaceMembers(activeWorkspace.uri.fsPath);
 		const isWorkspace = workspaceMembers.length > 0;
 
 		let targetCargoToml: string;
@@ -522,6 +553,215 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		runCargoCommandOnTargets('check', state.isReleaseMode, cargoTreeProvider, cargoTreeProvider.getSelectedWorkspaceMember());
 	});
 
+	// I copy the exact cargo command line(s) the current selection would run, ready to paste into CI or an issue.
+	// The environment cargUI sets on its terminals is prefixed, since the command behaves differently without it.
+	register('cargui.copyCommand', async () => {
+		const action = await vscode.window.showQuickPick(
+			['build', 'run', 'test', 'check', 'bench'],
+			{ placeHolder: 'Select the cargo command to copy' }
+		);
+
+		if (!action) {
+			return;
+		}
+
+		const workspace = cargoTreeProvider.getWorkspaceFolder();
+		const invocations = buildCargoCommandsOnTargets(action, state.isReleaseMode, cargoTreeProvider, cargoTreeProvider.getSelectedWorkspaceMember());
+		if (!workspace || invocations.length === 0) {
+			vscode.window.showErrorMessage('No targets found to build a command for');
+			return;
+		}
+
+		const commandText = invocations
+			.map(invocation => describeCargoInvocation(workspace.uri.fsPath, invocation.command))
+			.join('\n');
+		await vscode.env.clipboard.writeText(commandText);
+		vscode.window.showInformationMessage(
+			invocations.length === 1
+				? `Copied: ${commandText}`
+				: `Copied ${invocations.length} cargo commands to clipboard`
+		);
+	});
+
+	// I show exactly what Build/Run/Test/... would run, including the environment cargUI adds, without running anything.
+	register('cargui.previewCommand', async () => {
+		const action = await vscode.window.showQuickPick(
+			['build', 'run', 'test', 'check', 'bench'],
+			{ placeHolder: 'Select the cargo command to preview' }
+		);
+
+		if (!action) {
+			return;
+		}
+
+		const workspace = cargoTreeProvider.getWorkspaceFolder();
+		const selectedMember = cargoTreeProvider.getSelectedWorkspaceMember();
+		const invocations = buildCargoCommandsOnTargets(action, state.isReleaseMode, cargoTreeProvider, selectedMember);
+		if (!workspace || invocations.length === 0) {
+			vscode.window.showErrorMessage('No targets found to build a command for');
+			return;
+		}
+
+		const commandText = invocations
+			.map(invocation => describeCargoInvocation(workspace.uri.fsPath, invocation.command))
+			.join('\n');
+		outputChannel.appendLine(`Dry run: cargo ${action} in ${workspace.uri.fsPath}`);
+		for (const line of commandText.split('\n')) {
+			outputChannel.appendLine(`  ${line}`);
+		}
+		outputChannel.appendLine('');
+		outputChannel.show(true);
+
+		const choice = await vscode.window.showInformationMessage(
+			commandText,
+			{ modal: true, detail: 'Nothing has been run. The same commands are listed in the cargUI output.' },
+			'Copy',
+			'Run'
+		);
+		if (choice === 'Copy') {
+			await vscode.env.clipboard.writeText(commandText);
+		} else if (choice === 'Run') {
+			// I go through the regular runner so the checked-vs-selected member prompt and Re-run Last apply
+			await runCargoCommandOnTargets(action, state.isReleaseMode, cargoTreeProvider, selectedMember, invocations);
+		}
+	});
+
+	// I print one line per binary and compare it with the last recorded size so size regressions stand out.
+	// Sizes are keyed by (subcommand, target, binary path under the target directory), so debug, release and
+	// cross-compiled builds of the same target each keep their own history.
+	const reportArtifactSizes = async (title: string, subcommand: string, targetDir: string, artifacts: ArtifactSize[]) => {
+		const previousSizes = context.workspaceState.get<Record<string, number>>('cargui.artifactSizes', {});
+		const updatedSizes = { ...previousSizes };
+
+		outputChannel.appendLine(`${title} - ${new Date().toLocaleString()}`);
+		for (const artifact of artifacts) {
+			const binary = path.relative(targetDir, artifact.path).split(path.sep).join('/');
+			const sizeKey = `${subcommand}:${artifact.name}:${binary}`;
+			const previous = previousSizes[sizeKey];
+			let line = `  Binary: ${binary} — ${formatBytes(artifact.bytes)} (${artifact.bytes} bytes)`;
+			if (previous !== undefined) {
+				const diff = artifact.bytes - previous;
+				line += diff === 0
+					? ' (unchanged)'
+					: ` (${diff > 0 ? '+' : ''}${diff} bytes vs last build)`;
+			}
+			outputChannel.appendLine(line);
+			updatedSizes[sizeKey] = artifact.bytes;
+		}
+		outputChannel.appendLine('');
+
+		await context.workspaceState.update('cargui.artifactSizes', updatedSizes);
+	};
+
+	// I report the binaries on disk for the current mode, for builds that ran without JSON diagnostics.
+	register('cargui.artifactSizeReport', async () => {
+		const workspace = deps.getWorkspaceFolder();
+		if (!workspace) {
+			vscode.window.showErrorMessage('No workspace folder found');
+			return;
+		}
+
+		const profileDir = getProfileOutputDir(state.isReleaseMode);
+		const targetDir = await getTargetDirectory(workspace.uri.fsPath);
+		const outputRoot = getTargetOutputRoot(targetDir);
+		const artifacts = collectArtifactSizes(workspace.uri.fsPath, outputRoot, profileDir);
+		if (artifacts.length === 0) {
+			vscode.window.showInformationMessage(`No built binaries found in ${path.join(outputRoot, profileDir)} - run a ${profileDir} build first`);
+			return;
+		}
+
+		await reportArtifactSizes(`Artifact sizes (${profileDir})`, 'build', targetDir, artifacts);
+		outputChannel.show(true);
+	});
+
+	// I report sizes automatically after every successful build that ran with JSON diagnostics,
+	// since cargo then tells me exactly which executables it produced.
+	disposables.push(onDidFinishDiagnosticsRun(async run => {
+		const subcommand = getCargoSubcommand(run.command);
+		if (!run.success || subcommand !== 'build') {
+			return;
+		}
+		// Nothing was rebuilt, so the sizes can't have changed
+		if (run.artifacts.every(artifact => artifact.fresh)) {
+			return;
+		}
+		const artifacts = collectBuiltArtifactSizes(run.artifacts);
+		if (artifacts.length === 0) {
+			return;
+		}
+		const targetDir = await getTargetDirectory(run.cwd);
+		await reportArtifactSizes(`Artifact sizes after ${run.command}`, subcommand, targetDir, artifacts);
+	}));
+
+	register('cargui.openSessionLog', async () => {
+		const config = vscode.workspace.getConfiguration('cargui');
+		const configuredPath = config.get<string>('sessionLogPath', '').trim();
+		if (!configuredPath) {
+			const choice = await vscode.window.showInformationMessage(
+				'Session logging is off. Set cargui.sessionLogPath to record every cargo command cargUI runs.',
+				'Open Settings'
+			);
+			if (choice === 'Open Settings') {
+				vscode.commands.executeCommand('workbench.action.openSettings', 'cargui.sessionLogPath');
+			}
+			return;
+		}
+
+		const logPath = expandHomePath(configuredPath);
+		if (!fs.existsSync(logPath)) {
+			vscode.window.showInformationMessage(`No commands logged yet (${logPath})`);
+			return;
+		}
+
+		const document = await vscode.workspace.openTextDocument(logPath);
+		await vscode.window.showTextDocument(document);
+	});
+
+	// I let the user pick the .env file instead of typing its path into settings.
+	register('cargui.selectEnvFile', async () => {
+		const workspace = deps.getWorkspaceFolder();
+		if (!workspace) {
+			vscode.window.showErrorMessage('No workspace folder found');
+			return;
+		}
+
+		const config = vscode.workspace.getConfiguration('cargui');
+		const currentPath = config.get<string>('envFile', '').trim();
+		if (currentPath) {
+			const choice = await vscode.window.showQuickPick(
+				[
+					{ label: '$(folder-opened) Choose Another File', value: 'choose' },
+					{ label: '$(close) Stop Loading .env File', value: 'clear' }
+				],
+				{ placeHolder: `Currently loading ${currentPath}` }
+			);
+			if (!choice) {
+				return;
+			}
+			if (choice.value === 'clear') {
+				await config.update('envFile', undefined, vscode.ConfigurationTarget.Workspace);
+				vscode.window.showInformationMessage('Cargo commands will no longer load a .env file');
+				return;
+			}
+		}
+
+		const selection = await vscode.window.showOpenDialog({
+			defaultUri: workspace.uri,
+			canSelectMany: false,
+			openLabel: 'Use for Cargo Commands'
+		});
+		if (!selection || selection.length === 0) {
+			return;
+		}
+
+		// Store paths inside the package folder relative to it, so the setting survives moving the checkout
+		const selectedPath = selection[0].fsPath;
+		const relativePath = path.relative(workspace.uri.fsPath, selectedPath);
+		const storedPath = relativePath.startsWith('..') || path.isAbsolute(relativePath) ? selectedPath : relativePath;
+		await config.update('envFile', storedPath, vscode.ConfigurationTarget.Workspace);
+		vscode.window.showInformationMessage(`Cargo commands will load variables from ${storedPath}`);
+	});
+
 	register('cargui.clean', () => {
 		runCargoCommand('clean', false);
 	});
@@ -530,12 +770,99 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		runCargoCommand('fix', state.isReleaseMode);
 	});
 
+	// I preview clippy's machine-applicable suggestions so nothing gets rewritten before you confirm.
+	register('cargui.clippyFix', async () => {
+		const workspace = deps.getWorkspaceFolder();
+		if (!workspace) {
+			vscode.window.showErrorMessage('No workspace folder found');
+			return;
+		}
+
+		const selectedMember = cargoTreeProvider.getSelectedWorkspaceMember();
+		const packageArgs = selectedMember === 'all'
+			? '--workspace'
+			: selectedMember
+				? `--package ${selectedMember}`
+				: '';
+
+		let summary: ClippyFixSummary;
+		try {
+			summary = await vscode.window.withProgress({
+				location: vscode.ProgressLocation.Notification,
+				title: 'Collecting clippy suggestions...',
+				cancellable: false
+			}, () => collectClippyFixes(workspace.uri.fsPath, packageArgs));
+		} catch (error) {
+			vscode.window.showErrorMessage(`Failed to run cargo clippy: ${error}`);
+			return;
+		}
+
+		if (summary.fixCount === 0) {
+			vscode.window.showInformationMessage('Clippy found no automatically fixable suggestions');
+			return;
+		}
+
+		const fixLabel = summary.fixCount === 1 ? 'fix' : 'fixes';
+		const fileLabel = summary.files.length === 1 ? 'file' : 'files';
+		const choice = await vscode.window.showWarningMessage(
+			`${summary.fixCount} ${fixLabel} available in ${summary.files.length} ${fileLabel}. Apply them with cargo clippy --fix?`,
+			{ modal: true, detail: summary.files.join('\n') },
+			'Apply Fixes'
+		);
+
+		if (choice !== 'Apply Fixes') {
+			return;
+		}
+
+		sendCargoCommand('Cargo clippy --fix', workspace.uri.fsPath, `cargo clippy --fix --allow-dirty${packageArgs ? ` ${packageArgs}` : ''}${getSettingsCargoFlags('clippy')}`);
+	});
+
 	register('cargui.fmt', () => {
 		runCargoCommand('fmt', false);
 	});
 
 	register('cargui.doc', () => {
-		runCargoCommand('doc', state.isReleaseMode);
+		// cargo only launches the browser for --open once the docs build succeeds
+		const openAfterBuild = vscode.workspace.getConfiguration('cargui').get<boolean>('openDocsAfterBuild', false);
+		runCargoCommand('doc', state.isReleaseMode, openAfterBuild ? '--open' : undefined);
+	});
+
+	// I open already-built docs for the selected package without running cargo doc again.
+	register('cargui.openDocs', async () => {
+		const workspace = deps.getWorkspaceFolder();
+		if (!workspace) {
+			vscode.window.showErrorMessage('No workspace folder found');
+			return;
+		}
+
+		const selectedMember = cargoTreeProvider.getSelectedWorkspaceMember();
+		const memberPath = selectedMember && selectedMember !== 'all'
+			? getWorkspaceMembers(workspace.uri.fsPath).find(m => m.name === selectedMember)?.path
+			: undefined;
+		const targets = getCargoTargets(workspace.uri.fsPath, memberPath);
+		const docTarget = targets.find(t => t.type === 'lib') || targets.find(t => t.type === 'bin');
+		if (!docTarget) {
+			vscode.window.showErrorMessage('No library or binary target to open documentation for');
+			return;
+		}
+
+		const crateName = docTarget.name.replace(/-/g, '_');
+		const targetDir = getTargetOutputRoot(await getTargetDirectory(workspace.uri.fsPath));
+		const docPath = path.join(targetDir, 'doc', crateName, 'index.html');
+		if (fs.existsSync(docPath)) {
+			vscode.env.openExternal(vscode.Uri.file(docPath));
+			return;
+		}
+
+		const choice = await vscode.window.showInformationMessage(
+			`Documentation for ${crateName} hasn't been built yet`,
+			'Build and Open'
+		);
+		if (choice === 'Build and Open') {
+			const packageFlag = memberPath !== undefined ? ` -p ${selectedMember}` : '';
+			// Settings flags carry --target, so the docs land where we just looked for them
+			sendCargoCommand('Cargo doc', workspace.uri.fsPath, appendCommandArguments(`cargo doc --open${packageFlag}${getSettingsCargoFlags('doc')}`, 'doc'));
+		}
 	});
 
 	register('cargui.showRustupInfo', async () => {
@@ -573,8 +900,11 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 
 		quickPick.show();
 
+		const rustupWorkspace = deps.getWorkspaceFolder();
 		const [currentToolchain, toolchainInfos] = await Promise.all([
-			getCurrentToolchain(),
+			getCurrentToolchain(rustupWorkspace
+				? { cwd: rustupWorkspace.uri.fsPath, env: getCargoTerminalEnv(rustupWorkspace.uri.fsPath, true) }
+				: undefined),
 			checkRustupUpdates()
 		]);
 
@@ -732,13 +1062,275 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		vscode.window.showInformationMessage(`Updating ${channelsToUpdate.join(', ')} toolchain(s)...`);
 	});
 
-	register('cargui.toggleRelease', () => {
-		state.isReleaseMode = !state.isReleaseMode;
+	register('cargui.toggleRelease', async () => {
+		// Clicking the mode while a custom profile is active goes back to plain Debug/Release
+		const config = vscode.workspace.getConfiguration('cargui');
+		if (config.get<string>('customProfile', '').trim()) {
+			await config.update('customProfile', undefined, vscode.ConfigurationTarget.Workspace);
+		} else {
+			state.isReleaseMode = !state.isReleaseMode;
+		}
 		const mode = state.isReleaseMode ? 'Release' : 'Debug';
 		vscode.window.showInformationMessage(`Cargo build mode: ${mode}`);
 		cargoTreeProvider.refresh();
 	});
 
+	// I list the installed rustup targets; the choice is stored per workspace, so each project keeps its own.
+	register('cargui.selectTargetTriple', async () => {
+		const config = vscode.workspace.getConfiguration('cargui');
+		const currentTriple = config.get<string>('targetTriple', '').trim();
+		const installedTargets = await listInstalledTargets();
+
+		const items = [
+			{ label: 'Host', description: 'No --target (build for this machine)', value: '' },
+			...installedTargets.map(t => ({ label: t, description: '', value: t })),
+			{ label: '$(add) Install Another Target...', description: 'rustup target add', value: '__install__' }
+		].map(item => ({ ...item, label: item.value === currentTriple ? `$(check) ${item.label}` : item.label }));
+
+		const selected = await vscode.window.showQuickPick(items, { placeHolder: 'Select compilation target' });
+		if (!selected) {
+			return;
+		}
+
+		if (selected.value === '__install__') {
+			const triple = await vscode.window.showInputBox({
+				prompt: 'Target triple to install',
+				placeHolder: 'e.g., wasm32-unknown-unknown'
+			});
+			if (triple && triple.trim()) {
+				const terminal = vscode.window.createTerminal('Rustup Target');
+				terminal.show();
+				terminal.sendText(`rustup target add ${triple.trim()}`);
+			}
+			return;
+		}
+
+		await config.update('targetTriple', selected.value || undefined, vscode.ConfigurationTarget.Workspace);
+		cargoTreeProvider.refresh();
+	});
+
+	// I pin cargUI's cargo commands to one toolchain without touching the directory's rustup override.
+	register('cargui.selectToolchain', async () => {
+		const config = vscode.workspace.getConfiguration('cargui');
+		const currentToolchain = config.get<string>('toolchain', '').trim();
+		const toolchains = await listInstalledToolchains();
+
+		const items = [
+			{ label: 'Default', description: 'Use the rustup default or directory override', value: '' },
+			...toolchains.map(t => ({ label: t, description: '', value: t })),
+			{ label: '$(edit) Other...', description: 'Enter a toolchain name (e.g., nightly-2024-06-01)', value: '__custom__' }
+		].map(item => ({ ...item, label: item.value === currentToolchain ? `$(check) ${item.label}` : item.label }));
+
+		const selected = await vscode.window.showQuickPick(items, { placeHolder: 'Select toolchain for cargo commands' });
+		if (!selected) {
+			return;
+		}
+
+		let toolchain = selected.value;
+		if (toolchain === '__custom__') {
+			const input = await vscode.window.showInputBox({
+				prompt: 'Toolchain name',
+				placeHolder: 'e.g., nightly, 1.75.0, nightly-2024-06-01',
+				value: currentToolchain
+			});
+			if (input === undefined) {
+				return;
+			}
+			toolchain = input.trim();
+		}
+
+		await config.update('toolchain', toolchain || undefined, vscode.ConfigurationTarget.Workspace);
+		cargoTreeProvider.refresh();
+	});
+
+	// I edit RUSTFLAGS/RUSTDOCFLAGS per workspace, so they don't have to be exported in the shell VS Code was launched from.
+	register('cargui.editRustflags', async () => {
+		const config = vscode.workspace.getConfiguration('cargui');
+		const variables = [
+			{ label: 'RUSTFLAGS', description: config.get<string>('rustflags', '') || 'not set', setting: 'rustflags' },
+			{ label: 'RUSTDOCFLAGS', description: config.get<string>('rustdocflags', '') || 'not set', setting: 'rustdocflags' }
+		];
+		const selected = await vscode.window.showQuickPick(variables, { placeHolder: 'Select variable to edit' });
+		if (!selected) {
+			return;
+		}
+
+		const value = await vscode.window.showInputBox({
+			prompt: `${selected.label} for cargo commands in this workspace (leave empty to unset)`,
+			placeHolder: selected.setting === 'rustflags' ? 'e.g., -C target-cpu=native' : 'e.g., --cfg docsrs',
+			value: config.get<string>(selected.setting, '')
+		});
+		if (value === undefined) {
+			return;
+		}
+
+		await config.update(selected.setting, value.trim() || undefined, vscode.ConfigurationTarget.Workspace);
+		vscode.window.showInformationMessage(value.trim() ? `${selected.label} set to: ${value.trim()}` : `${selected.label} unset`);
+	});
+
+	register('cargui.setJobs', async () => {
+		const config = vscode.workspace.getConfiguration('cargui');
+		const currentJobs = config.get<number>('jobs', 0);
+		const input = await vscode.window.showInputBox({
+			prompt: `Parallel jobs for cargo (-j). Leave empty for auto (${os.cpus().length} CPUs)`,
+			placeHolder: 'auto',
+			value: currentJobs > 0 ? String(currentJobs) : '',
+			validateInput: text => {
+				if (text.trim() && !/^[1-9]\d*$/.test(text.trim())) {
+					return 'Enter a positive whole number, or leave empty for auto';
+				}
+				return null;
+			}
+		});
+		if (input === undefined) {
+			return;
+		}
+
+		const jobs = input.trim() ? parseInt(input.trim(), 10) : undefined;
+		await config.update('jobs', jobs, vscode.ConfigurationTarget.Workspace);
+		vscode.window.showInformationMessage(jobs ? `Cargo will use ${jobs} parallel job(s)` : 'Cargo will pick the number of parallel jobs');
+	});
+
+	// I keep extra arguments per cargo command, so e.g. check can always get --all-targets.
+	register('cargui.editCommandArguments', async () => {
+		const config = vscode.workspace.getConfiguration('cargui');
+		const commandArguments = { ...config.get<{ [action: string]: string }>('commandArguments', {}) };
+		const actions = ['build', 'run', 'test', 'check', 'bench', 'doc', 'fix', 'clean', 'update'];
+
+		const selected = await vscode.window.showQuickPick(
+			actions.map(action => ({ label: action, description: commandArguments[action] || '' })),
+			{ placeHolder: 'Select command to set extra arguments for' }
+		);
+		if (!selected) {
+			return;
+		}
+
+		const value = await vscode.window.showInputBox({
+			prompt: `Extra cargo arguments for every cargo ${selected.label} (leave empty to remove)`,
+			placeHolder: 'e.g., --all-targets',
+			value: commandArguments[selected.label] || ''
+		});
+		if (value === undefined) {
+			return;
+		}
+
+		if (value.trim()) {
+			commandArguments[selected.label] = value.trim();
+		} else {
+			delete commandArguments[selected.label];
+		}
+		await config.update('commandArguments', commandArguments, vscode.ConfigurationTarget.Workspace);
+		vscode.window.showInformationMessage(
+			value.trim() ? `cargo ${selected.label} will add: ${value.trim()}` : `Removed extra arguments for cargo ${selected.label}`
+		);
+	});
+
+	// I manage the --config KEY=VALUE list; picking an existing entry removes it.
+	register('cargui.editConfigOverrides', async () => {
+		const config = vscode.workspace.getConfiguration('cargui');
+		const overrides = config.get<string[]>('configOverrides', []);
+
+		const items = [
+			{ label: '$(add) Add Override...', description: '', value: '' },
+			...overrides.map(o => ({ label: `$(trash) ${o}`, description: 'Remove', value: o }))
+		];
+		const selected = await vscode.window.showQuickPick(items, {
+			placeHolder: overrides.length > 0 ? 'Add a --config override or pick one to remove' : 'No --config overrides yet'
+		});
+		if (!selected) {
+			return;
+		}
+
+		if (selected.value) {
+			await config.update('configOverrides', overrides.filter(o => o !== selected.value), vscode.ConfigurationTarget.Workspace);
+			vscode.window.showInformationMessage(`Removed --config ${selected.value}`);
+			return;
+		}
+
+		const input = await vscode.window.showInputBox({
+			prompt: 'Cargo config override in KEY=VALUE form (VALUE is TOML)',
+			placeHolder: 'e.g., build.rustc-wrapper="sccache"',
+			validateInput: text => {
+				if (!/^[A-Za-z0-9_.\-"]+\s*=\s*\S/.test(text.trim())) {
+					return 'Use KEY=VALUE, e.g. net.git-fetch-with-cli=true';
+				}
+				if (text.includes("'")) {
+					return 'Use double quotes for TOML strings';
+				}
+				return null;
+			}
+		});
+		if (!input) {
+			return;
+		}
+
+		await config.update('configOverrides', [...overrides, input.trim()], vscode.ConfigurationTarget.Workspace);
+		vscode.window.showInformationMessage(`Cargo commands will use --config ${input.trim()}`);
+	});
+
+	// I keep --locked/--offline/--frozen as one choice; --frozen already means --locked plus --offline.
+	register('cargui.selectNetworkMode', async () => {
+		const config = vscode.workspace.getConfiguration('cargui');
+		const currentMode = config.get<string>('networkMode', 'default');
+		const modes = [
+			{ label: 'Default', description: 'Update Cargo.lock and use the network as needed', value: 'default' },
+			{ label: 'Locked', description: '--locked: fail if Cargo.lock needs changes', value: 'locked' },
+			{ label: 'Offline', description: '--offline: never access the network', value: 'offline' },
+			{ label: 'Frozen', description: '--frozen: locked and offline', value: 'frozen' }
+		];
+		const selected = await vscode.window.showQuickPick(
+			modes.map(mode => ({ ...mode, label: mode.value === currentMode ? `$(check) ${mode.label}` : mode.label })),
+			{ placeHolder: 'Select network and lockfile behavior for cargo' }
+		);
+		if (!selected || selected.value === currentMode) {
+			return;
+		}
+
+		await config.update('networkMode', selected.value, vscode.ConfigurationTarget.Workspace);
+	});
+
+	// I offer Debug, Release and every [profile.*] declared in the workspace Cargo.toml.
+	register('cargui.selectProfile', async () => {
+		const workspace = deps.getWorkspaceFolder();
+		if (!workspace) {
+			vscode.window.showErrorMessage('No workspace folder found');
+			return;
+		}
+
+		const config = vscode.workspace.getConfiguration('cargui');
+		const currentProfile = config.get<string>('customProfile', '').trim();
+		const metadata = await getCargoMetadata(workspace.uri.fsPath);
+		const customProfiles = (metadata?.profiles ?? []).filter(p => !['dev', 'release', 'test', 'bench'].includes(p));
+
+		const items = [
+			{ label: 'Debug', description: 'dev profile', value: 'debug' },
+			{ label: 'Release', description: '--release', value: 'release' },
+			...customProfiles.map(p => ({ label: p, description: `--profile ${p}`, value: p }))
+		].map(item => {
+			const isCurrent = currentProfile
+				? item.value === currentProfile
+				: item.value === (state.isReleaseMode ? 'release' : 'debug');
+			return { ...item, label: isCurrent ? `$(check) ${item.label}` : item.label };
+		});
+
+		const selected = await vscode.window.showQuickPick(items, {
+			placeHolder: customProfiles.length > 0
+				? 'Select build profile'
+				: 'Select build profile (add [profile.<name>] to Cargo.toml for custom profiles)'
+		});
+		if (!selected) {
+			return;
+		}
+
+		if (selected.value === 'debug' || selected.value === 'release') {
+			await config.update('customProfile', undefined, vscode.ConfigurationTarget.Workspace);
+			state.isReleaseMode = selected.value === 'release';
+		} else {
+			await config.update('customProfile', selected.value, vscode.ConfigurationTarget.Workspace);
+		}
+		cargoTreeProvider.refresh();
+	});
+
 	register('cargui.runTarget', (target: CargoTreeItem) => {
 		if (target && target.target) {
 			runCargoTarget(target.target.name, target.target.type, state.isReleaseMode, cargoTreeProvider, target.target.requiredFeatures);
@@ -758,28 +1350,52 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		if (!workspaceFolder) {
 			return;
 		}
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const memberPath = state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all'
 			? members.find(m => m.name === state.selectedWorkspaceMember)?.path
 			: undefined;
-		const features = discoverCargoFeatures(workspaceFolder.uri.fsPath, memberPath);
+		const features = getCargoFeatures(workspaceFolder.uri.fsPath, memberPath);
 		const checkedFeatures = cargoTreeProvider.getCheckedFeatures();
 		const shouldCheckAll = checkedFeatures.length < features.length;
 		features.forEach(feature => cargoTreeProvider.setFeatureChecked(feature, shouldCheckAll));
 	});
 
+	// I keep default/no-default/all as one choice, so --all-features and --no-default-features can't be combined.
+	register('cargui.selectFeatureMode', async () => {
+		const config = vscode.workspace.getConfiguration('cargui');
+		const currentMode = config.get<string>('featureMode', 'default');
+		const modes = [
+			{ label: 'Default Features', description: 'Enable the package\'s default features', value: 'default' },
+			{ label: 'No Default Features', description: '--no-default-features', value: 'noDefaultFeatures' },
+			{ label: 'All Features', description: '--all-features', value: 'allFeatures' }
+		];
+		const selected = await vscode.window.showQuickPick(
+			modes.map(mode => ({ ...mode, label: mode.value === currentMode ? `$(check) ${mode.label}` : mode.label })),
+			{ placeHolder: 'Select how cargo enables features' }
+		);
+		if (!selected || selected.value === currentMode) {
+			return;
+		}
+
+		await config.update('featureMode', selected.value, vscode.ConfigurationTarget.Workspace);
+		if (selected.value === 'allFeatures' && cargoTreeProvider.getCheckedFeatures().length > 0) {
+			vscode.window.showInformationMessage('All features are enabled; checked features have no extra effect until you switch back');
+		}
+		cargoTreeProvider.refresh();
+	});
+
 	register('cargui.toggleAllTargets', () => {
 		const workspaceFolder = deps.getWorkspaceFolder();
 		if (!workspaceFolder) {
 			return;
 		}
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const memberPath = state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all'
 			? members.find(m => m.name === state.selectedWorkspaceMember)?.path
 			: undefined;
 		const targets = state.selectedWorkspaceMember === 'all'
 			? []
-			: discoverCargoTargets(workspaceFolder.uri.fsPath, memberPath);
+			: getCargoTargets(workspaceFolder.uri.fsPath, memberPath);
 		const checkedTargets = cargoTreeProvider.getCheckedTargets();
 		const shouldCheckAll = checkedTargets.length < targets.length;
 		targets.forEach(target => cargoTreeProvider.setChecked(target.name, shouldCheckAll));
@@ -790,7 +1406,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		if (!workspaceFolder) {
 			return;
 		}
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const checkedMembers = cargoTreeProvider.getCheckedWorkspaceMembers();
 		const shouldCheckAll = checkedMembers.length < members.length;
 		members.forEach(member => cargoTreeProvider.setWorkspaceMemberChecked(member.name, shouldCheckAll));
@@ -801,7 +1417,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		if (!workspaceFolder) {
 			return;
 		}
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const dependencyMemberPath = state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all'
 			? members.find(m => m.name === state.selectedWorkspaceMember)?.path
 			: undefined;
@@ -822,7 +1438,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		if (!workspaceFolder || !item.categoryName) {
 			return;
 		}
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const dependencyMemberPath = state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all'
 			? members.find(m => m.name === state.selectedWorkspaceMember)?.path
 			: undefined;
@@ -864,7 +1480,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			state.selectedWorkspaceMember = 'all';
 			vscode.window.showInformationMessage('Selected: All workspace members (--workspace)');
 		} else {
-			const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+			const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 			const member = members.find(m => m.name === memberName);
 			if (member) {
 				state.selectedWorkspaceMember = memberName;
@@ -876,7 +1492,15 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 	});
 
 	register('cargui.selectWorkspaceFolder', async () => {
-		if (!vscode.workspace.workspaceFolders || vscode.workspace.workspaceFolders.length <= 1) {
+		// The watch terminal keeps rebuilding the folder it was started in, so switching now would leave it out of sync
+		if (state.isWatchMode) {
+			const choice = await vscode.window.showWarningMessage(
+				'Cannot switch package folder while watch mode is running',
+				'Stop Watch Mode'
+			);
+			if (choice === 'Stop Watch Mode') {
+				await vscode.commands.executeCommand('cargui.toggleWatch');
+			}
 			return;
 		}
 
@@ -885,7 +1509,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		const currentIndex = context.workspaceState.get<number>('cargui.selectedWorkspaceFolder', 0);
 
 		// Sort folders by access history
-		const sortedFolders = vscode.workspace.workspaceFolders
+		const sortedFolders = (vscode.workspace.workspaceFolders ?? [])
 			.map((folder, index) => ({ folder, index }))
 			.sort((a, b) => {
 				// Current folder always last
@@ -906,13 +1530,52 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			description: index === currentIndex ? '$(check) current' : '',
 			index: index
 		}));
+		// index -1 marks the folder picker entry
+		items.push({ label: '$(folder-opened) Browse...', description: 'Open another package folder', index: -1 });
 
 		const selected = await vscode.window.showQuickPick(items, {
 			placeHolder: 'Select package folder to view'
 		});
 
-		if (selected) {
+		if (!selected) {
+			return;
+		}
+		if (selected.index !== -1) {
 			await deps.selectWorkspaceFolder(selected.index);
+			return;
+		}
+
+		const picked = await vscode.window.showOpenDialog({
+			canSelectFiles: false,
+			canSelectFolders: true,
+			canSelectMany: false,
+			openLabel: 'Select Package Folder'
+		});
+		if (!picked || picked.length === 0) {
+			return;
+		}
+
+		const folderUri = picked[0];
+		if (!fs.existsSync(path.join(folderUri.fsPath, 'Cargo.toml'))) {
+			vscode.window.showErrorMessage(`No Cargo.toml found in ${folderUri.fsPath}`);
+			return;
+		}
+
+		const existingFolder = vscode.workspace.getWorkspaceFolder(folderUri);
+		if (existingFolder && existingFolder.uri.fsPath === folderUri.fsPath) {
+			await deps.selectWorkspaceFolder(existingFolder.index);
+			return;
+		}
+
+		// Switch to the folder once VS Code has actually added it
+		const newIndex = vscode.workspace.workspaceFolders?.length ?? 0;
+		const listener = vscode.workspace.onDidChangeWorkspaceFolders(async () => {
+			listener.dispose();
+			await deps.selectWorkspaceFolder(newIndex);
+		});
+		if (!vscode.workspace.updateWorkspaceFolders(newIndex, 0, { uri: folderUri })) {
+			listener.dispose();
+			vscode.window.showErrorMessage(`Failed to add ${folderUri.fsPath} to the workspace`);
 		}
 	});
 
@@ -928,7 +1591,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			vscode.window.showInformationMessage(`Deselected workspace member: ${memberName}`);
 		} else {
 			// Otherwise select the new member
-			const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+			const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 			const member = members.find(m => m.name === memberName);
 			if (member) {
 				state.selectedWorkspaceMember = memberName;
@@ -957,7 +1620,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 
 		let cargoTomlUri: vscode.Uri;
 		if (target.workspaceMember) {
-			const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+			const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 			const member = members.find(m => m.name === target.workspaceMember);
 			if (member) {
 				cargoTomlUri = vscode.Uri.file(path.join(workspaceFolder.uri.fsPath, member.path, 'Cargo.toml'));
@@ -989,7 +1652,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 
 		let basePath = workspaceFolder.uri.fsPath;
 		if (target.workspaceMember) {
-			const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+			const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 			const member = members.find(m => m.name === target.workspaceMember);
 			if (member) {
 				// Construct absolute path: workspace root + member relative path
@@ -1028,7 +1691,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			return;
 		}
 
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const member = members.find(m => m.name === item.workspaceMember);
 		
 		if (!member) {
@@ -1100,7 +1763,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		
 		if (hasNameMismatch) {
 			// Fix name in Cargo.toml to match filename
-			const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+			const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 			const member = item.workspaceMember 
 				? members.find(m => m.name === item.workspaceMember)
 				: undefined;
@@ -1169,7 +1832,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		}
 
 		// Get workspace member if applicable
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const member = item.workspaceMember 
 			? members.find(m => m.name === item.workspaceMember)
 			: undefined;
@@ -1263,7 +1926,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		}
 
 		// Get workspace member path (used by both options)
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const member = item.workspaceMember 
 			? members.find(m => m.name === item.workspaceMember)
 			: undefined;
@@ -2234,12 +2897,44 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			return;
 		}
 
-		const terminal = vscode.window.createTerminal({
-			name: `Cargo: ${cmd.name}`,
-			cwd: workspace.uri.fsPath
-		});
-		terminal.show();
-		terminal.sendText(cmd.command);
+		// I catch subcommands the active toolchain doesn't have (e.g. miri on stable) before the terminal fails.
+		// Commands that pin a toolchain with +nightly are left alone.
+		const subcommandMatch = cmd.command.trim().match(/^cargo\s+([a-z][\w-]*)/);
+		if (subcommandMatch) {
+			const subcommand = subcommandMatch[1];
+			// I ask with the terminal's environment so cargui.toolchain (RUSTUP_TOOLCHAIN) is taken into account
+			const toolchainContext = { cwd: workspace.uri.fsPath, env: getCargoTerminalEnv(workspace.uri.fsPath, true) };
+			const available = await listCargoSubcommands(toolchainContext);
+			if (available && !available.includes(subcommand)) {
+				const toolchain = await getCurrentToolchain(toolchainContext);
+				const choice = await vscode.window.showWarningMessage(
+					`'cargo ${subcommand}' is not available in the current toolchain (${toolchain}). It may need nightly or a separate install.`,
+					'Switch to Nightly',
+					'Run Anyway'
+				);
+				if (!choice) {
+					return;
+				}
+				if (choice === 'Switch to Nightly') {
+					// RUSTUP_TOOLCHAIN beats a rustup override, so when cargui.toolchain is set I switch that instead
+					const config = vscode.workspace.getConfiguration('cargui');
+					if (config.get<string>('toolchain', '').trim()) {
+						await config.update('toolchain', 'nightly', vscode.ConfigurationTarget.Workspace);
+						cargoTreeProvider.refresh();
+						vscode.window.showInformationMessage(`${workspace.name} now runs cargo commands with the nightly toolchain (cargui.toolchain)`);
+					} else {
+						if (!await setToolchainOverride(workspace.uri.fsPath, 'nightly')) {
+							vscode.window.showErrorMessage('Failed to set nightly override. Is the nightly toolchain installed?');
+							return;
+						}
+						updateToolchainStatusBar();
+						vscode.window.showInformationMessage(`${workspace.name} now uses the nightly toolchain (rustup override)`);
+					}
+				}
+			}
+		}
+
+		sendCargoCommand(`Cargo: ${cmd.name}`, workspace.uri.fsPath, cmd.command);
 	});
 
 	register('cargui.createSnapshot', async () => {
@@ -2265,7 +2960,9 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 				arguments: cargoTreeProvider.getCheckedArguments(),
 				envVars: cargoTreeProvider.getCheckedEnvVars(),
 				workspaceMember: state.selectedWorkspaceMember,
-				checkedWorkspaceMembers: cargoTreeProvider.getCheckedWorkspaceMembers()
+				checkedWorkspaceMembers: cargoTreeProvider.getCheckedWorkspaceMembers(),
+				customProfile: config.get<string>('customProfile', '').trim() || undefined,
+				targetTriple: config.get<string>('targetTriple', '').trim() || undefined
 			};
 
 			const updatedSnapshots = [...snapshots, newSnapshot];
@@ -2297,11 +2994,13 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		}
 
 		state.isReleaseMode = snapshot.mode === 'release';
+		await config.update('customProfile', snapshot.customProfile, vscode.ConfigurationTarget.Workspace);
+		await config.update('targetTriple', snapshot.targetTriple, vscode.ConfigurationTarget.Workspace);
 
 		if (snapshot.workspaceMember) {
 			const workspace = deps.getWorkspaceFolder();
 			if (workspace) {
-				const workspaceMembers = discoverWorkspaceMembers(workspace.uri.fsPath);
+				const workspaceMembers = getWorkspaceMembers(workspace.uri.fsPath);
 				if (
 					snapshot.workspaceMember === 'all' ||
 					workspaceMembers.some(m => m.name === snapshot.workspaceMember)
@@ -2334,7 +3033,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		if (snapshot.checkedWorkspaceMembers) {
 			const workspace = deps.getWorkspaceFolder();
 			if (workspace) {
-				const workspaceMembers = discoverWorkspaceMembers(workspace.uri.fsPath);
+				const workspaceMembers = getWorkspaceMembers(workspace.uri.fsPath);
 				snapshot.checkedWorkspaceMembers.forEach(m => {
 					if (workspaceMembers.some(wm => wm.name === m)) {
 						cargoTreeProvider.setWorkspaceMemberChecked(m, true);
@@ -2399,7 +3098,9 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 				arguments: cargoTreeProvider.getCheckedArguments(),
 				envVars: cargoTreeProvider.getCheckedEnvVars(),
 				workspaceMember: state.selectedWorkspaceMember,
-				checkedWorkspaceMembers: cargoTreeProvider.getCheckedWorkspaceMembers()
+				checkedWorkspaceMembers: cargoTreeProvider.getCheckedWorkspaceMembers(),
+				customProfile: config.get<string>('customProfile', '').trim() || undefined,
+				targetTriple: config.get<string>('targetTriple', '').trim() || undefined
 			};
 		} else {
 			updatedSnapshot = { ...snapshot, name: newName.trim() };
@@ -2526,29 +3227,28 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		}
 
 		state.watchAction = selected.value;
-		const terminal = vscode.window.createTerminal({
-			name: `Cargo Watch: ${state.watchAction}`,
-			cwd: workspace.uri.fsPath
-		});
-		state.watchTerminal = terminal;
 
-		let command = `cargo watch -x ${state.watchAction}`;
-		if (state.isReleaseMode && ['run', 'build', 'test'].includes(state.watchAction)) {
-			command = `cargo watch -x "${state.watchAction} --release"`;
-		}
+		// I build the -x command like the toolbar does, so profile, target, feature mode and overrides apply on every rerun
+		let watchArgs = state.watchAction;
+		watchArgs += getProfileFlag(state.watchAction, state.isReleaseMode && ['run', 'build', 'test'].includes(state.watchAction));
+		watchArgs += getSettingsCargoFlags(state.watchAction);
 
 		const checkedFeatures = cargoTreeProvider.getCheckedFeatures();
 		if (checkedFeatures.length > 0) {
-			command = `cargo watch -x "${state.watchAction} --features ${checkedFeatures.join(',')}"`;
+			watchArgs += ` --features ${checkedFeatures.join(',')}`;
 		}
 
+		let command = `cargo watch -x ${quoteShellArgument(watchArgs)}`;
+
 		const checkedEnvVars = cargoTreeProvider.getCheckedEnvVars();
 		if (checkedEnvVars.length > 0) {
 			command = `${checkedEnvVars.join(' ')} ${command}`;
 		}
 
-		terminal.show();
-		terminal.sendText(command);
+		state.watchTerminal = sendCargoCommand(`Cargo Watch: ${state.watchAction}`, workspace.uri.fsPath, command, false);
+		if (!state.watchTerminal) {
+			return;
+		}
 		state.isWatchMode = true;
 		cargoTreeProvider.refresh();
 		vscode.window.showInformationMessage(`Watch mode started: ${state.watchAction}`);
@@ -2572,7 +3272,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		let memberEditionInfo;
 		let memberPath: string | undefined;
 		if (state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all') {
-			const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+			const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 			const member = members.find(m => m.name === state.selectedWorkspaceMember);
 			if (member) {
 				memberPath = path.join(workspaceFolder.uri.fsPath, member.path);
@@ -2663,13 +3363,44 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			return;
 		}
 
+		// I list editions newest first, with cargo's own default on top so Enter keeps today's behavior
+		const editions = (await getAvailableEditions()).slice().reverse();
+		const edition = await vscode.window.showQuickPick(
+			[
+				{ label: 'Default', description: "cargo's default edition", value: '' },
+				...editions.map(e => ({ label: e, description: '', value: e }))
+			],
+			{ placeHolder: 'Select Rust edition' }
+		);
+
+		if (!edition) {
+			return;
+		}
+
+		const vcs = await vscode.window.showQuickPick(
+			[
+				{ label: 'git', description: 'Initialize a git repository (default)', value: '' },
+				{ label: 'none', description: 'No version control', value: 'none' },
+				{ label: 'hg', description: 'Mercurial', value: 'hg' },
+				{ label: 'pijul', description: 'Pijul', value: 'pijul' },
+				{ label: 'fossil', description: 'Fossil', value: 'fossil' }
+			],
+			{ placeHolder: 'Select version control' }
+		);
+
+		if (!vcs) {
+			return;
+		}
+
 		const isLib = projectType === 'Library';
 		const libFlag = isLib ? '--lib' : '--bin';
+		const editionFlag = edition.value ? ` --edition ${edition.value}` : '';
+		const vcsFlag = vcs.value ? ` --vcs ${vcs.value}` : '';
 		const projectPath = path.join(targetFolder[0].fsPath, projectName);
 
 		const terminal = vscode.window.createTerminal('Cargo New');
 		terminal.show();
-		terminal.sendText(`cd "${targetFolder[0].fsPath}" && cargo new ${libFlag} ${projectName}`);
+		terminal.sendText(`cd "${targetFolder[0].fsPath}" && cargo new ${libFlag}${editionFlag}${vcsFlag} ${projectName}`);
 
 		vscode.window.showInformationMessage(
 			`Creating ${isLib ? 'library' : 'binary'} package: ${projectName}`,
@@ -2689,6 +3420,15 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		runCargoCommand('update', false);
 	});
 
+	// I re-send the last action's exact command lines, even if checkboxes have changed since.
+	register('cargui.rerunLast', () => {
+		if (!rerunLastCargoCommands()) {
+			vscode.window.showInformationMessage('No cargo command has been run yet');
+			return;
+		}
+		vscode.window.setStatusBarMessage('$(sync) Re-running last cargo command', 3000);
+	});
+
 	register('cargui.formatCargoToml', async () => {
 		const workspace = deps.getWorkspaceFolder();
 		if (!workspace) {
@@ -2696,7 +3436,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			return;
 		}
 
-		const workspaceMembers = discoverWorkspaceMembers(workspace.uri.fsPath);
+		const workspaceMembers = getWorkspaceMembers(workspace.uri.fsPath);
 
 		// If this is a workspace (has members), show quick pick regardless of selection
 		if (workspaceMembers.length > 1) {
@@ -2901,7 +3641,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 
 			let cargoTomlPath: string;
 			if (state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all') {
-				const workspaceMembers = discoverWorkspaceMembers(workspace.uri.fsPath);
+				const workspaceMembers = getWorkspaceMembers(workspace.uri.fsPath);
 				const member = workspaceMembers.find(m => m.name === state.selectedWorkspaceMember);
 				if (!member) {
 					vscode.window.showErrorMessage('Selected workspace member not found');
@@ -2943,7 +3683,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			return;
 		}
 
-		const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+		const members = getWorkspaceMembers(workspace.uri.fsPath);
 		let cargoTomlPath: string;
 		if (state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all') {
 			const member = members.find(m => m.name === state.selectedWorkspaceMember);
@@ -3068,7 +3808,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			return;
 		}
 
-		const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+		const members = getWorkspaceMembers(workspace.uri.fsPath);
 		let cargoTomlPath: string;
 		
 		// Workspace dependencies are always in the root, regardless of selected member
@@ -3180,7 +3920,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			return;
 		}
 
-		const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+		const members = getWorkspaceMembers(workspace.uri.fsPath);
 		let cargoTomlPath: string;
 		if (state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all') {
 			const member = members.find(m => m.name === state.selectedWorkspaceMember);
@@ -3323,7 +4063,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 			return;
 		}
 
-		const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+		const members = getWorkspaceMembers(workspace.uri.fsPath);
 		let cargoTomlPath: string;
 		if (state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all') {
 			const member = members.find(m => m.name === state.selectedWorkspaceMember);
@@ -3397,7 +4137,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		}
 
 		// we determine which Cargo.toml to edit
-		const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+		const members = getWorkspaceMembers(workspace.uri.fsPath);
 		let cargoTomlPath: string;
 		if (memberPath) {
 			cargoTomlPath = path.join(workspace.uri.fsPath, memberPath, 'Cargo.toml');
@@ -3479,7 +4219,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 
 		// we get all undeclared features (not just checked ones)
 		const featureMemberPath = memberPath || (state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all'
-			? discoverWorkspaceMembers(workspace.uri.fsPath).find(m => m.name === state.selectedWorkspaceMember)?.path
+			? getWorkspaceMembers(workspace.uri.fsPath).find(m => m.name === state.selectedWorkspaceMember)?.path
 			: undefined);
 		
 		const undeclaredFeatures = detectUndeclaredFeatures(workspace.uri.fsPath, featureMemberPath);
@@ -3490,7 +4230,7 @@ export function registerCommands(deps: CommandDependencies): vscode.Disposable[]
 		}
 
 		// we determine which Cargo.toml to edit
-		const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+		const members = getWorkspaceMembers(workspace.uri.fsPath);
 		let cargoTomlPath: string;
 		if (featureMemberPath) {
 			cargoTomlPath = path.join(workspace.uri.fsPath, featureMemberPath, 'Cargo.toml');
@@ -3592,7 +4332,7 @@ register('cargui.declareModule', async (item: CargoTreeItem) => {
 	}
 
 	// we determine which main target file to edit (main.rs or lib.rs)
-	const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+	const members = getWorkspaceMembers(workspace.uri.fsPath);
 	let targetPath: string;
 	let memberPath = '';
 
@@ -3668,7 +4408,7 @@ register('cargui.declareAllUndeclaredModules', async () => {
 	}
 
 	// we get all undeclared modules - if multi-member workspace and no specific member selected, process all
-	const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+	const members = getWorkspaceMembers(workspace.uri.fsPath);
 	const allUndeclaredModules: Array<{ module: ModuleInfo, memberPath: string, memberName: string }> = [];
 
 	if (members.length > 1 && (!state.selectedWorkspaceMember || state.selectedWorkspaceMember === 'all')) {
@@ -3901,7 +4641,7 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 		const workspaceCargoToml = path.join(workspace.uri.fsPath, 'Cargo.toml');
 		let memberCargoToml: string | undefined;
 		if (state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all') {
-			const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+			const members = getWorkspaceMembers(workspace.uri.fsPath);
 			const member = members.find(m => m.name === state.selectedWorkspaceMember);
 			if (member) {
 				memberCargoToml = path.join(workspace.uri.fsPath, member.path, 'Cargo.toml');
@@ -4045,7 +4785,7 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 			return;
 		}
 
-		const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+		const members = getWorkspaceMembers(workspace.uri.fsPath);
 		const member = members.find(m => m.name === state.selectedWorkspaceMember);
 		if (!member) {
 			vscode.window.showErrorMessage('Selected workspace member not found');
@@ -4160,6 +4900,23 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 		await addDependencyWithName(undefined, 'workspace');
 	});
 
+	// I open a plain shell in the selected package folder, for cargo subcommands cargUI has no button for.
+	register('cargui.openTerminal', () => {
+		const workspace = deps.getWorkspaceFolder();
+		if (!workspace) {
+			vscode.window.showErrorMessage('No workspace folder found');
+			return;
+		}
+		const terminal = vscode.window.createTerminal({ name: workspace.name, cwd: workspace.uri.fsPath });
+		terminal.show();
+	});
+
+	// I re-read every Cargo.toml on demand, for edits the file watchers can't see (e.g. generated manifests).
+	register('cargui.refresh', () => {
+		invalidateCargoMetadata();
+		cargoTreeProvider.refresh();
+	});
+
 	register('cargui.showKeybindings', () => {
 		vscode.commands.executeCommand('workbench.action.openGlobalKeybindings', 'cargui');
 	});
@@ -4239,7 +4996,7 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 			return;
 		}
 
-		const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+		const members = getWorkspaceMembers(workspace.uri.fsPath);
 		const targetMemberPath = state.selectedWorkspaceMember && state.selectedWorkspaceMember !== 'all'
 			? members.find(m => m.name === state.selectedWorkspaceMember)?.path
 			: undefined;
@@ -4299,6 +5056,26 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 		}
 	});
 
+	// I reveal the package's Cargo.toml in the system file manager (Finder, Explorer, or the Linux default).
+	register('cargui.revealPackageInOS', async () => {
+		const workspace = deps.getWorkspaceFolder();
+		if (!workspace) {
+			vscode.window.showErrorMessage('No workspace folder found');
+			return;
+		}
+
+		let packagePath = workspace.uri.fsPath;
+		const selectedMember = cargoTreeProvider.getSelectedWorkspaceMember();
+		if (selectedMember && selectedMember !== 'all') {
+			const member = getWorkspaceMembers(workspace.uri.fsPath).find(m => m.name === selectedMember);
+			if (member) {
+				packagePath = path.join(workspace.uri.fsPath, member.path);
+			}
+		}
+
+		await vscode.commands.executeCommand('revealFileInOS', vscode.Uri.file(path.join(packagePath, 'Cargo.toml')));
+	});
+
 	// We open the root workspace Cargo.toml file in the editor when the package header is clicked
 	register('cargui.openProjectCargoToml', async (memberName: string | null | undefined) => {
 		console.log('[cargUI] openProjectCargoToml called with memberName:', memberName);
@@ -4321,7 +5098,7 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 		// we check if a member name was resolved (from argument or tree provider)
 		if (resolvedMemberName && resolvedMemberName !== 'all') {
 			// we open the selected member's Cargo.toml
-			const members = discoverWorkspaceMembers(workspace.uri.fsPath);
+			const members = getWorkspaceMembers(workspace.uri.fsPath);
 			const member = members.find(m => m.name === resolvedMemberName);
 			
 			if (member) {
@@ -4368,7 +5145,7 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 		// Determine the target based on context
 		let memberPath = '';
 		if (item?.workspaceMember) {
-			const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+			const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 			const member = members.find(m => m.name === item.workspaceMember);
 			if (!member) {
 				vscode.window.showErrorMessage(`Workspace member "${item.workspaceMember}" not found`);
@@ -4445,7 +5222,7 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 			// we need to find which member/package this module belongs to
 			// by checking which member has this module in their src directory
 			let packageName = '';
-			const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+			const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 			
 			for (const member of members) {
 				const memberPath = path.join(workspaceFolder.uri.fsPath, member.path);
@@ -4479,18 +5256,12 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 			const modulePath = moduleName.replace(/\.rs$/, '').replace(/\//g, '/');
 			
 			// we build local documentation first
-			const terminal = vscode.window.createTerminal({
-				name: `Cargo Doc - ${packageName}`,
-				cwd: workspaceFolder.uri.fsPath
-			});
-			terminal.show();
-			terminal.sendText(`cargo doc -p ${packageName} --no-deps`);
+			sendCargoCommand(`Cargo Doc - ${packageName}`, workspaceFolder.uri.fsPath, `cargo doc -p ${packageName} --no-deps${getSettingsCargoFlags('doc')}`);
 
 			// we then open the specific module's documentation
-			setTimeout(() => {
+			setTimeout(async () => {
 				const docPath = path.join(
-					workspaceFolder.uri.fsPath,
-					'target',
+					getTargetOutputRoot(await getTargetDirectory(workspaceFolder.uri.fsPath)),
 					'doc',
 					crateName,
 					modulePath,
@@ -4517,7 +5288,7 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 		}
 
 		// we use the explicitly selected member from the tree provider
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const selectedMemberInfo = members.find(m => m.name === selectedMember);
 		
 		if (!selectedMemberInfo) {
@@ -4544,18 +5315,12 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 		const crateName = packageName.replace(/-/g, '_');
 
 		// we build local documentation first
-		const terminal = vscode.window.createTerminal({
-			name: `Cargo Doc - ${packageName}`,
-			cwd: workspaceFolder.uri.fsPath
-		});
-		terminal.show();
-		terminal.sendText(`cargo doc -p ${packageName} --no-deps`);
+		sendCargoCommand(`Cargo Doc - ${packageName}`, workspaceFolder.uri.fsPath, `cargo doc -p ${packageName} --no-deps${getSettingsCargoFlags('doc')}`);
 
 		// we then open the documentation at the correct path (not relying on --open which might open a binary)
-		setTimeout(() => {
+		setTimeout(async () => {
 			const docPath = path.join(
-				workspaceFolder.uri.fsPath,
-				'target',
+				getTargetOutputRoot(await getTargetDirectory(workspaceFolder.uri.fsPath)),
 				'doc',
 				crateName,
 				'index.html'
@@ -4576,7 +5341,7 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 			return;
 		}
 
-		const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+		const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 		const member = members.find(m => m.name === item.workspaceMember);
 		if (!member) {
 			vscode.window.showErrorMessage(`Workspace member "${item.workspaceMember}" not found`);
@@ -4616,21 +5381,17 @@ register('cargui.removeEnvironmentVariable', async (item: CargoTreeItem) => {
 		const memberName = item.workspaceMember;
 		
 		// we build the documentation (no-deps to skip dependencies, making it faster)
-		const buildCmd = `cargo doc -p ${memberName} --no-deps`;
+		const buildCmd = `cargo doc -p ${memberName} --no-deps${getSettingsCargoFlags('doc')}`;
 		
 		// we create a terminal to run the command
-		const terminal = vscode.window.createTerminal({
-			name: `Cargo Doc - ${memberName}`,
-			cwd: workspaceFolder.uri.fsPath
-		});
-		terminal.show();
-		terminal.sendText(buildCmd);
+		sendCargoCommand(`Cargo Doc - ${memberName}`, workspaceFolder.uri.fsPath, buildCmd);
 
 		// we open the documentation in browser after build completes
-		// cargo doc generates docs at target/doc/<crate_name>/index.html
+		// cargo doc generates docs at <target_dir>[/<triple>]/doc/<crate_name>/index.html
 		// crate names convert hyphens to underscores, so we need to check both forms
-		setTimeout(() => {
-			const docDir = path.join(workspaceFolder.uri.fsPath, 'target', 'doc');
+		setTimeout(async () => {
+			// the target directory and --target triple from settings decide where the docs land
+			const docDir = path.join(getTargetOutputRoot(await getTargetDirectory(workspaceFolder.uri.fsPath)), 'doc');
 			
 			// we convert package name (with hyphens) to crate name (with underscores)
 			const crateName = memberName.replace(/-/g, '_');
@@ -4966,7 +5727,7 @@ Start with the module header (if needed), then document elements in order of app
 		// If the target belongs to a workspace member, include that in the path
 		let basePath = workspaceFolder.uri.fsPath;
 		if (item.workspaceMember) {
-			const members = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+			const members = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 			const member = members.find(m => m.name === item.workspaceMember);
 			if (member) {
 				basePath = path.join(workspaceFolder.uri.fsPath, member.path);
@@ -1,6 +1,6 @@
 import * as vscode from 'vscode';
 import { ArgumentCategory, CustomCommand, CustomCommandCategory, Snapshot } from './types';
-import { discoverWorkspaceMembers, discoverCargoTargets } from './cargoDiscovery';
+import { getWorkspaceMembers, getCargoTargets } from './cargoMetadata';
 import { CargoTreeDataProvider } from './cargoTreeProvider';
 
 /**
@@ -91,11 +91,11 @@ export async function initializeDefaultConfig(
     const snapshotsInspection = config.inspect<Snapshot[]>('snapshots');
     if (!snapshotsInspection?.workspaceValue && !snapshotsInspection?.workspaceFolderValue) {
         const defaultSnapshots: Snapshot[] = [];
-        const workspaceMembers = discoverWorkspaceMembers(workspaceFolder.uri.fsPath);
+        const workspaceMembers = getWorkspaceMembers(workspaceFolder.uri.fsPath);
 
         if (workspaceMembers.length > 0) {
             for (const member of workspaceMembers) {
-                const memberTargets = discoverCargoTargets(workspaceFolder.uri.fsPath, member.path);
+                const memberTargets = getCargoTargets(workspaceFolder.uri.fsPath, member.path);
                 const mainTarget = memberTargets.find(t => t.type === 'bin' && t.path === 'src/main.rs');
                 const libTarget = memberTargets.find(t => t.type === 'lib' && t.path === 'src/lib.rs');
 
@@ -135,7 +135,7 @@ export async function initializeDefaultConfig(
                 }
             }
         } else {
-            const targets = discoverCargoTargets(workspaceFolder.uri.fsPath);
+            const targets = getCargoTargets(workspaceFolder.uri.fsPath);
             const mainTarget = targets.find(t => t.type === 'bin' && t.path === 'src/main.rs');
             const libTarget = targets.find(t => t.type === 'lib' && t.path === 'src/lib.rs');
 
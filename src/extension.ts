@@ -10,10 +10,12 @@ import { DependencyDecorationProvider } from './decorationProvider';
 import { CargoTreeDataProvider } from './cargoTreeProvider';
 import {
     discoverWorkspaceMembers,
-    discoverCargoTargets
+    discoverCargoTargets,
+    findParentWorkspaceRoot
 } from './cargoDiscovery';
 import {
     getCurrentToolchain,
+    isCargoAvailable,
     startRustupUpdateChecker,
     stopRustupUpdateChecker
 } from './rustup';
@@ -23,6 +25,9 @@ import { runSmartDetection, showConfigureUnregisteredUI } from './smartDetection
 import { moveFileToTargetDirectory } from './fileOperations';
 import { formatCargoTomlFile, applyCargoTomlChanges } from './cargoToml';
 import { initializeDefaultConfig } from './defaultConfig';
+import { invalidateCargoMetadata } from './cargoMetadata';
+import { getCargoTerminalEnv, setCargoMissing } from './cargoCommands';
+import { onDidUpdateDiagnosticsRun, onDidFinishDiagnosticsRun, toVsCodeDiagnostics, countDiagnostics, CargoDiagnosticsRun } from './cargoDiagnostics';
 
 let isReleaseMode = false;
 let isWatchMode = false;
@@ -102,18 +107,148 @@ export async function activate(context: vscode.ExtensionContext) {
     rustToolchainStatusBar.tooltip = 'Click to view Rust toolchain details';
     context.subscriptions.push(rustToolchainStatusBar);
 
+    // Ask rustup and cargo the way cargo terminals run: in the package folder, with RUSTUP_TOOLCHAIN from cargui.toolchain
+    const getToolchainContext = () => {
+        const folderPath = cargoTreeProvider.getWorkspaceFolder()?.uri.fsPath;
+        return folderPath ? { cwd: folderPath, env: getCargoTerminalEnv(folderPath, true) } : undefined;
+    };
+
+    // Hide cargo command buttons and block cargo commands when cargo can't be launched.
+    // Messages are shown when the state changes, not on every toolchain refresh, unless the user asks to retry.
+    let lastCargoState: string | undefined;
+    const checkCargoAvailability = async (forceMessage: boolean = false) => {
+        const toolchainContext = getToolchainContext();
+        let cargoState = await isCargoAvailable(toolchainContext) ? 'available' : 'missing';
+
+        // RUSTUP_TOOLCHAIN naming a toolchain that isn't installed makes cargo fail too, but cargo itself is fine
+        const configuredToolchain = vscode.workspace.getConfiguration('cargui').get<string>('toolchain', '').trim();
+        if (cargoState === 'missing' && configuredToolchain && await isCargoAvailable({ cwd: toolchainContext?.cwd })) {
+            cargoState = `toolchain:${configuredToolchain}`;
+        }
+
+        const cargoMissing = cargoState === 'missing';
+        vscode.commands.executeCommand('setContext', 'cargui.cargoMissing', cargoMissing);
+        setCargoMissing(cargoMissing);
+        const stateChanged = lastCargoState !== cargoState;
+        lastCargoState = cargoState;
+        if (cargoState === 'available' || (!stateChanged && !forceMessage)) {
+            return;
+        }
+
+        if (!cargoMissing) {
+            const choice = await vscode.window.showWarningMessage(
+                `cargUI: The toolchain "${configuredToolchain}" from cargui.toolchain is not installed, so cargo commands will fail.`,
+                'Select Toolchain',
+                'Retry'
+            );
+            if (choice === 'Select Toolchain') {
+                vscode.commands.executeCommand('cargui.selectToolchain');
+            } else if (choice === 'Retry') {
+                checkCargoAvailability(true);
+            }
+            return;
+        }
+
+        const choice = await vscode.window.showErrorMessage(
+            'cargUI: cargo was not found in PATH. Cargo commands are disabled until it is installed.',
+            'Install Rust',
+            'Retry'
+        );
+        if (choice === 'Install Rust') {
+            vscode.env.openExternal(vscode.Uri.parse('https://rustup.rs'));
+        } else if (choice === 'Retry') {
+            checkCargoAvailability(true);
+        }
+    };
+
     const updateToolchainStatusBar = async () => {
-        const toolchain = await getCurrentToolchain();
+        const toolchain = await getCurrentToolchain(getToolchainContext());
         if (toolchain !== 'unknown') {
             rustToolchainStatusBar.text = `$(tools) ${toolchain}`;
             rustToolchainStatusBar.show();
         } else {
             rustToolchainStatusBar.hide();
         }
+        // A toolchain change can add or remove cargo from PATH
+        await checkCargoAvailability();
     };
 
     updateToolchainStatusBar();
 
+    // Offline status bar: cargo commands won't fetch from the network in offline/frozen mode
+    const networkModeStatusBar = vscode.window.createStatusBarItem(vscode.StatusBarAlignment.Right, 99);
+    networkModeStatusBar.command = 'cargui.selectNetworkMode';
+    context.subscriptions.push(networkModeStatusBar);
+
+    const updateNetworkModeStatusBar = () => {
+        const networkMode = vscode.workspace.getConfiguration('cargui').get<string>('networkMode', 'default');
+        if (networkMode === 'offline' || networkMode === 'frozen') {
+            networkModeStatusBar.text = networkMode === 'frozen' ? '$(lock) Cargo Frozen' : '$(cloud) Cargo Offline';
+            networkModeStatusBar.tooltip = `cargo runs with --${networkMode}. Click to change`;
+            networkModeStatusBar.show();
+        } else {
+            networkModeStatusBar.hide();
+        }
+    };
+    updateNetworkModeStatusBar();
+
+    const configurationChangeDisposable = vscode.workspace.onDidChangeConfiguration(event => {
+        if (event.affectsConfiguration('cargui.networkMode')) {
+            updateNetworkModeStatusBar();
+        }
+        // Covers Select Toolchain as well as edits to settings.json
+        if (event.affectsConfiguration('cargui.toolchain')) {
+            updateToolchainStatusBar();
+        }
+    });
+    context.subscriptions.push(configurationChangeDisposable);
+
+    // JSON diagnostics: publish compiler messages to the Problems panel and show counts in the status bar
+    const cargoDiagnostics = vscode.languages.createDiagnosticCollection('cargui');
+    const diagnosticsStatusBar = vscode.window.createStatusBarItem(vscode.StatusBarAlignment.Left, 0);
+    diagnosticsStatusBar.command = 'workbench.actions.view.problems';
+    context.subscriptions.push(cargoDiagnostics, diagnosticsStatusBar);
+
+    // One action sends an invocation per checked target, so concurrent runs are merged instead of replacing each other
+    let trackedRuns: CargoDiagnosticsRun[] = [];
+    const runningRuns = new Set<CargoDiagnosticsRun>();
+
+    const publishDiagnosticsRuns = () => {
+        const diagnostics = toVsCodeDiagnostics(trackedRuns);
+        cargoDiagnostics.clear();
+        for (const [filePath, fileDiagnostics] of diagnostics) {
+            cargoDiagnostics.set(vscode.Uri.file(filePath), fileDiagnostics);
+        }
+
+        const { errors, warnings } = countDiagnostics(diagnostics);
+        const counts = `${errors} error${errors === 1 ? '' : 's'}, ${warnings} warning${warnings === 1 ? '' : 's'}`;
+        const failed = errors > 0 || trackedRuns.some(run => !runningRuns.has(run) && !run.success);
+        const icon = runningRuns.size > 0 ? '$(sync~spin)' : failed ? '$(error)' : warnings > 0 ? '$(warning)' : '$(check)';
+        diagnosticsStatusBar.text = `${icon} ${counts}`;
+        const commands = trackedRuns.map(run => `${run.command}${runningRuns.has(run) ? ' (running)' : ''}`);
+        diagnosticsStatusBar.tooltip = `${commands.join('\n')}\nClick to open the Problems panel`;
+        diagnosticsStatusBar.show();
+    };
+    context.subscriptions.push(
+        onDidUpdateDiagnosticsRun(run => {
+            if (!trackedRuns.includes(run)) {
+                // A run that starts while nothing else is running belongs to a new action, which replaces the old results
+                if (runningRuns.size === 0) {
+                    trackedRuns = [];
+                }
+                trackedRuns.push(run);
+                runningRuns.add(run);
+            }
+            publishDiagnosticsRuns();
+        }),
+        onDidFinishDiagnosticsRun(run => {
+            runningRuns.delete(run);
+            if (trackedRuns.includes(run)) {
+                publishDiagnosticsRuns();
+            }
+        })
+    );
+
     if (workspaceFolder) {
         // Initialize default configuration
         await initializeDefaultConfig(workspaceFolder, cargoTreeProvider);
@@ -137,13 +272,39 @@ export async function activate(context: vscode.ExtensionContext) {
         // Trigger initial refresh to populate tree and run smart detection
         cargoTreeProvider.refresh();
 
+        // Offer the enclosing Cargo workspace when a member package was opened on its own
+        const parentWorkspaceRoot = findParentWorkspaceRoot(workspaceFolder.uri.fsPath);
+        if (parentWorkspaceRoot && !context.workspaceState.get('cargui.ignoreParentWorkspace', false)) {
+            vscode.window.showInformationMessage(
+                `This package is a member of the Cargo workspace at ${parentWorkspaceRoot}`,
+                'Open Workspace Root',
+                'Add to Workspace',
+                "Don't Show Again"
+            ).then(choice => {
+                const rootUri = vscode.Uri.file(parentWorkspaceRoot);
+                if (choice === 'Open Workspace Root') {
+                    vscode.commands.executeCommand('vscode.openFolder', rootUri);
+                } else if (choice === 'Add to Workspace') {
+                    const folderCount = vscode.workspace.workspaceFolders?.length ?? 0;
+                    vscode.workspace.updateWorkspaceFolders(folderCount, 0, { uri: rootUri });
+                } else if (choice === "Don't Show Again") {
+                    context.workspaceState.update('cargui.ignoreParentWorkspace', true);
+                }
+            });
+        }
+
         // Watch all Cargo.toml files (root and workspace members)
         const cargoTomlWatcher = vscode.workspace.createFileSystemWatcher(
             new vscode.RelativePattern(workspaceFolder, '**/Cargo.toml')
         );
-        cargoTomlWatcher.onDidChange(() => cargoTreeProvider.refresh());
-        cargoTomlWatcher.onDidCreate(() => cargoTreeProvider.refresh());
-        cargoTomlWatcher.onDidDelete(() => cargoTreeProvider.refresh());
+        // Manifest edits can change members, targets and features, so cached cargo metadata is dropped first
+        const onCargoTomlChanged = () => {
+            invalidateCargoMetadata();
+            cargoTreeProvider.refresh();
+        };
+        cargoTomlWatcher.onDidChange(onCargoTomlChanged);
+        cargoTomlWatcher.onDidCreate(onCargoTomlChanged);
+        cargoTomlWatcher.onDidDelete(onCargoTomlChanged);
         context.subscriptions.push(cargoTomlWatcher);
 
         const targetsWatcher = vscode.workspace.createFileSystemWatcher(
@@ -174,11 +335,36 @@ export async function activate(context: vscode.ExtensionContext) {
         }
         // If a Cargo.toml was saved, refresh immediately to update targets/features
         if (document.fileName.endsWith('Cargo.toml')) {
+            invalidateCargoMetadata();
             cargoTreeProvider.refresh();
         }
     });
     context.subscriptions.push(textDocumentSaveDisposable);
 
+    // Adding or removing folders can change which package is active, so rediscover members and targets
+    const workspaceFoldersDisposable = vscode.workspace.onDidChangeWorkspaceFolders(async event => {
+        const folders = vscode.workspace.workspaceFolders ?? [];
+        vscode.commands.executeCommand('setContext', 'cargui.hasMultipleWorkspaceFolders', folders.length > 1);
+
+        const activeFolder = cargoTreeProvider.getWorkspaceFolder();
+        if (activeFolder && folders.length > 0) {
+            const isSameFolder = (folder: vscode.WorkspaceFolder) => folder.uri.toString() === activeFolder.uri.toString();
+            if (event.removed.some(isSameFolder)) {
+                // The active folder is gone, so fall back to the first remaining one
+                await selectWorkspaceFolder(0);
+                return;
+            }
+            // Removing an earlier folder shifts indexes, so store the active folder's new position
+            const index = folders.findIndex(isSameFolder);
+            if (index >= 0) {
+                await context.workspaceState.update('cargui.selectedWorkspaceFolder', index);
+                cargoTreeProvider.setWorkspaceContext(folders[index], context);
+            }
+        }
+        cargoTreeProvider.refresh();
+    });
+    context.subscriptions.push(workspaceFoldersDisposable);
+
     const terminalCloseDisposable = vscode.window.onDidCloseTerminal(terminal => {
         if (terminal === watchTerminal) {
             isWatchMode = false;
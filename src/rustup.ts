@@ -1,16 +1,34 @@
 import * as vscode from 'vscode';
 import { RustupToolchainInfo } from './types';
 
+/**
+ * Where and with which environment to ask rustup or cargo, so the answer matches what cargUI's cargo terminals get:
+ * the package folder (for `rustup override` and rust-toolchain.toml) and the variables from getCargoTerminalEnv
+ * (for `RUSTUP_TOOLCHAIN` from `cargui.toolchain`).
+ */
+export interface ToolchainContext {
+    cwd?: string;
+    env?: Record<string, string>;
+}
+
+function toExecOptions(context?: ToolchainContext): { cwd?: string; env?: NodeJS.ProcessEnv } {
+    return {
+        cwd: context?.cwd,
+        env: context?.env ? { ...process.env, ...context.env } : undefined
+    };
+}
+
 /**
  * Gets the currently active Rust toolchain using rustup.
  * 
+ * @param context - Folder and environment to resolve the toolchain for; defaults to the extension host's
  * @returns Promise resolving to the toolchain name (e.g., "stable-x86_64-apple-darwin") or "unknown" if not found
  */
-export async function getCurrentToolchain(): Promise<string> {
+export async function getCurrentToolchain(context?: ToolchainContext): Promise<string> {
     return new Promise((resolve) => {
         const { exec } = require('child_process');
         
-        exec('rustup show active-toolchain', (error: any, stdout: string, stderr: string) => {
+        exec('rustup show active-toolchain', toExecOptions(context), (error: any, stdout: string, stderr: string) => {
             if (error) {
                 console.error('Failed to get current toolchain:', error);
                 resolve('unknown');
@@ -28,6 +46,113 @@ export async function getCurrentToolchain(): Promise<string> {
     });
 }
 
+/**
+ * Checks whether `cargo` can be launched from the extension host's PATH.
+ * 
+ * @param context - Folder and environment to check in, so a missing `cargui.toolchain` is caught too
+ * @returns Promise resolving to true if `cargo --version` runs successfully
+ */
+export async function isCargoAvailable(context?: ToolchainContext): Promise<boolean> {
+    return new Promise((resolve) => {
+        const { exec } = require('child_process');
+        
+        exec('cargo --version', toExecOptions(context), (error: any) => {
+            resolve(!error);
+        });
+    });
+}
+
+/**
+ * Lists the cargo subcommands available to the toolchain active in a directory,
+ * including installed third-party subcommands and aliases.
+ * 
+ * @param context - Folder and environment of the cargo terminal the command would run in
+ * @returns Promise resolving to the subcommand names, or undefined if `cargo --list` failed
+ */
+export async function listCargoSubcommands(context: ToolchainContext): Promise<string[] | undefined> {
+    return new Promise((resolve) => {
+        const { exec } = require('child_process');
+        
+        exec('cargo --list', toExecOptions(context), (error: any, stdout: string) => {
+            if (error) {
+                resolve(undefined);
+                return;
+            }
+            
+            // Output format: "Installed Commands:" followed by indented "    build    Compile the current package" lines
+            const subcommands = stdout
+                .split('\n')
+                .filter(line => /^\s+\S/.test(line))
+                .map(line => line.trim().split(/\s+/)[0]);
+            resolve(subcommands);
+        });
+    });
+}
+
+/**
+ * Lists the compilation targets installed for the active toolchain.
+ * 
+ * @returns Promise resolving to target triples (e.g., "wasm32-unknown-unknown"), or an empty array if rustup failed
+ */
+export async function listInstalledTargets(): Promise<string[]> {
+    return new Promise((resolve) => {
+        const { exec } = require('child_process');
+        
+        exec('rustup target list --installed', (error: any, stdout: string) => {
+            if (error) {
+                console.error('Failed to list installed targets:', error);
+                resolve([]);
+                return;
+            }
+            resolve(stdout.split('\n').map(line => line.trim()).filter(line => line.length > 0));
+        });
+    });
+}
+
+/**
+ * Lists the toolchains installed with rustup.
+ * 
+ * @returns Promise resolving to toolchain names (e.g., "stable-x86_64-apple-darwin"), or an empty array if rustup failed
+ */
+export async function listInstalledToolchains(): Promise<string[]> {
+    return new Promise((resolve) => {
+        const { exec } = require('child_process');
+        
+        exec('rustup toolchain list', (error: any, stdout: string) => {
+            if (error) {
+                console.error('Failed to list toolchains:', error);
+                resolve([]);
+                return;
+            }
+            // Output format: "stable-x86_64-apple-darwin (default)" - we keep only the name
+            resolve(stdout
+                .split('\n')
+                .map(line => line.trim().split(/\s+/)[0])
+                .filter(name => name && name.length > 0));
+        });
+    });
+}
+
+/**
+ * Sets a directory's rustup toolchain override (`rustup override set <toolchain>`).
+ * 
+ * @param cwd - Directory to set the override for
+ * @param toolchain - Toolchain name (e.g., "nightly")
+ * @returns Promise resolving to true if the override was set
+ */
+export async function setToolchainOverride(cwd: string, toolchain: string): Promise<boolean> {
+    return new Promise((resolve) => {
+        const { exec } = require('child_process');
+        
+        exec(`rustup override set ${toolchain}`, { cwd }, (error: any, stdout: string, stderr: string) => {
+            if (error) {
+                console.error('Failed to set toolchain override:', stderr || error);
+            }
+            resolve(!error);
+        });
+    });
+}
+
 /**
  * Checks for available rustup toolchain updates across all installed channels.
  * 
@@ -108,6 +108,8 @@ export interface Snapshot {
     envVars: string[];
     workspaceMember?: string;
     checkedWorkspaceMembers?: string[];
+    customProfile?: string;  // cargui.customProfile when the snapshot was taken (replaces mode)
+    targetTriple?: string;   // cargui.targetTriple when the snapshot was taken
 }
 
 export interface CustomCommand {
@@ -159,3 +161,105 @@ export interface RustupToolchainInfo {
     availableVersion?: string;
     hasUpdate: boolean;
 }
+
+/**
+ * A target as reported by `cargo metadata` (kinds are e.g. "bin", "lib", "example", "test", "bench", "custom-build").
+ */
+export interface CargoMetadataTarget {
+    name: string;
+    kind: string[];
+    src_path: string;
+    required_features?: string[];
+}
+
+/**
+ * A package as reported by `cargo metadata`.
+ */
+export interface CargoMetadataPackage {
+    name: string;
+    version: string;
+    id: string;
+    manifest_path: string;
+    edition: string;
+    rust_version?: string | null;
+    targets: CargoMetadataTarget[];
+    features: { [feature: string]: string[] };
+}
+
+/**
+ * The parts of `cargo metadata --no-deps --format-version 1` output cargUI uses,
+ * plus the `[profile.*]` names declared in the root Cargo.toml (cargo metadata doesn't report profiles).
+ */
+export interface CargoMetadata {
+    packages: CargoMetadataPackage[];
+    workspace_members: string[];
+    workspace_default_members?: string[];
+    workspace_root: string;
+    target_directory: string;
+    profiles: string[];
+}
+
+/**
+ * A source location attached to a compiler diagnostic. Lines and columns are 1-based.
+ */
+export interface CargoDiagnosticSpan {
+    file_name: string;
+    line_start: number;
+    line_end: number;
+    column_start: number;
+    column_end: number;
+    is_primary: boolean;
+    label?: string | null;
+    suggested_replacement?: string | null;
+    suggestion_applicability?: string | null;
+}
+
+/**
+ * A rustc or clippy diagnostic. `rendered` is the text rustc would have printed (with ANSI colors
+ * when cargo runs with `--message-format=json-diagnostic-rendered-ansi`); children are notes and help lines.
+ */
+export interface CargoDiagnostic {
+    message: string;
+    code?: { code: string; explanation?: string | null } | null;
+    level: string;
+    spans: CargoDiagnosticSpan[];
+    children: CargoDiagnostic[];
+    rendered?: string | null;
+}
+
+/**
+ * A `compiler-message` line from cargo's JSON output.
+ */
+export interface CargoCompilerMessage {
+    reason: 'compiler-message';
+    package_id: string;
+    manifest_path: string;
+    target: CargoMetadataTarget;
+    message: CargoDiagnostic;
+}
+
+/**
+ * A `compiler-artifact` line, emitted once per compiled (or already fresh) target.
+ */
+export interface CargoCompilerArtifact {
+    reason: 'compiler-artifact';
+    package_id: string;
+    manifest_path: string;
+    target: CargoMetadataTarget;
+    filenames: string[];
+    executable?: string | null;
+    fresh: boolean;
+}
+
+/**
+ * The final `build-finished` line.
+ */
+export interface CargoBuildFinished {
+    reason: 'build-finished';
+    success: boolean;
+}
+
+/**
+ * The cargo JSON messages cargUI understands. Other reasons (e.g. build-script-executed) are skipped by the parser.
+ */
+export type CargoMessage = CargoCompilerMessage | CargoCompilerArtifact | CargoBuildFinished;